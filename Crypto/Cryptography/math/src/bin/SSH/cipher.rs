@@ -0,0 +1,144 @@
+use aes::Aes128;
+use cbc::cipher::block_padding::NoPadding;
+use cbc::cipher::{BlockCipher, BlockDecryptMut, BlockEncryptMut, KeyInit, KeyIvInit};
+use des::{Des, TdesEde3};
+use rand::Rng;
+
+// --- Block Ciphers for the Oracle ---
+// The paper attacks SSH/TLS records protected by DES/3DES (8-byte blocks);
+// AES (16-byte blocks) is included to show the attack does not depend on the block size.
+#[derive(Clone, Copy, Debug)]
+pub enum CipherKind {
+    Des,
+    TripleDes,
+    Aes128,
+}
+
+impl CipherKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CipherKind::Des => "DES-CBC",
+            CipherKind::TripleDes => "3DES-CBC",
+            CipherKind::Aes128 => "AES-128-CBC",
+        }
+    }
+
+    pub fn block_size(&self) -> usize {
+        match self {
+            CipherKind::Des | CipherKind::TripleDes => 8,
+            CipherKind::Aes128 => 16,
+        }
+    }
+
+    pub fn key_size(&self) -> usize {
+        match self {
+            CipherKind::Des => 8,
+            CipherKind::TripleDes => 24, // EDE with three independent keys
+            CipherKind::Aes128 => 16,
+        }
+    }
+}
+
+/// A keyed block cipher used in CBC mode.
+/// Inputs must already be padded to a multiple of the block size;
+/// padding is the oracle's job, not the cipher's.
+pub struct CbcCipher {
+    kind: CipherKind,
+    key: Vec<u8>,
+}
+
+impl CbcCipher {
+    pub fn random(kind: CipherKind, rng: &mut impl Rng) -> Self {
+        let mut key = vec![0u8; kind.key_size()];
+        rng.fill(key.as_mut_slice());
+        CbcCipher { kind, key }
+    }
+
+    pub fn kind(&self) -> CipherKind {
+        self.kind
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.kind.block_size()
+    }
+
+    /// y_j = E(x_j XOR y_{j-1}), with y_0 = IV
+    pub fn encrypt(&self, iv: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        assert_eq!(plaintext.len() % self.block_size(), 0, "plaintext must be block aligned");
+        match self.kind {
+            CipherKind::Des => cbc_encrypt::<Des>(&self.key, iv, plaintext),
+            CipherKind::TripleDes => cbc_encrypt::<TdesEde3>(&self.key, iv, plaintext),
+            CipherKind::Aes128 => cbc_encrypt::<Aes128>(&self.key, iv, plaintext),
+        }
+    }
+
+    /// x_j = D(y_j) XOR y_{j-1}, with y_0 = IV
+    pub fn decrypt(&self, iv: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+        assert_eq!(ciphertext.len() % self.block_size(), 0, "ciphertext must be block aligned");
+        match self.kind {
+            CipherKind::Des => cbc_decrypt::<Des>(&self.key, iv, ciphertext),
+            CipherKind::TripleDes => cbc_decrypt::<TdesEde3>(&self.key, iv, ciphertext),
+            CipherKind::Aes128 => cbc_decrypt::<Aes128>(&self.key, iv, ciphertext),
+        }
+    }
+}
+
+fn cbc_encrypt<C>(key: &[u8], iv: &[u8], plaintext: &[u8]) -> Vec<u8>
+where
+    C: BlockCipher + BlockEncryptMut + KeyInit,
+{
+    cbc::Encryptor::<C>::new_from_slices(key, iv)
+        .expect("key and IV sizes come from CipherKind")
+        .encrypt_padded_vec_mut::<NoPadding>(plaintext)
+}
+
+fn cbc_decrypt<C>(key: &[u8], iv: &[u8], ciphertext: &[u8]) -> Vec<u8>
+where
+    C: BlockCipher + BlockDecryptMut + KeyInit,
+{
+    cbc::Decryptor::<C>::new_from_slices(key, iv)
+        .expect("key and IV sizes come from CipherKind")
+        .decrypt_padded_vec_mut::<NoPadding>(ciphertext)
+        .expect("NoPadding never fails on block-aligned input")
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    #[test]
+    fn decrypt_reverses_encrypt() {
+        let mut rng = StdRng::seed_from_u64(41);
+        for kind in [CipherKind::Des, CipherKind::TripleDes, CipherKind::Aes128] {
+            let cipher = CbcCipher::random(kind, &mut rng);
+            let iv = vec![7u8; kind.block_size()];
+            let plaintext: Vec<u8> = (0..4 * kind.block_size() as u8).collect();
+            let ciphertext = cipher.encrypt(&iv, &plaintext);
+            assert_eq!(ciphertext.len(), plaintext.len());
+            assert_ne!(ciphertext, plaintext);
+            assert_eq!(cipher.decrypt(&iv, &ciphertext), plaintext);
+        }
+    }
+
+    #[test]
+    fn a_flipped_iv_bit_flips_the_same_plaintext_bit() {
+        // x_1 = D(y_1) XOR IV: the malleability the padding oracle attack is built on
+        let cipher = CbcCipher::random(CipherKind::Des, &mut StdRng::seed_from_u64(41));
+        let (iv, plaintext) = ([0u8; 8], *b"ATTACK AT DAWN!!");
+        let ciphertext = cipher.encrypt(&iv, &plaintext);
+        let mut forged_iv = iv;
+        forged_iv[7] ^= 0x01;
+        let decrypted = cipher.decrypt(&forged_iv, &ciphertext);
+        assert_eq!(&decrypted[..8], b"ATTACK @"); // 'A' ^ 0x01
+        assert_eq!(&decrypted[8..], &plaintext[8..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "block aligned")]
+    fn unaligned_input_is_rejected() {
+        CbcCipher::random(CipherKind::Aes128, &mut StdRng::seed_from_u64(41)).encrypt(&[0; 16], &[0; 15]);
+    }
+}
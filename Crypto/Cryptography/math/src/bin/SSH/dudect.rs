@@ -0,0 +1,72 @@
+use rand::Rng;
+
+// --- Leak Detection (Reparaz, Balasch & Verbauwhede, "dude, is my code constant time?" 2017) ---
+// Time the target on two classes of inputs, picked at random for every measurement so that
+// drift (CPU frequency, other processes) hits both classes alike, then compare the two means
+// with Welch's t-test. |t| above 4.5 is the usual "it leaks" line: a false alarm that large
+// has probability below 1e-5. A constant-time implementation should stay well under it.
+
+pub const T_THRESHOLD: f64 = 4.5;
+
+pub struct LeakReport {
+    pub samples: [usize; 2],
+    pub means: [f64; 2],
+    pub t: f64,
+}
+
+impl LeakReport {
+    pub fn leaks(&self) -> bool {
+        self.t.abs() > T_THRESHOLD
+    }
+}
+
+/// Takes `samples` measurements, each from a randomly chosen class (0 or 1).
+/// `time(class)` runs the target once on an input of that class and returns how long it took.
+pub fn measure(samples: usize, mut time: impl FnMut(usize) -> f64) -> LeakReport {
+    let mut classes: [Vec<f64>; 2] = [Vec::new(), Vec::new()];
+    let mut rng = rand::thread_rng();
+    for _ in 0..samples {
+        let class = rng.gen_range(0..2);
+        classes[class].push(time(class));
+    }
+    let [a, b] = &classes;
+    LeakReport { samples: [a.len(), b.len()], means: [mean(a), mean(b)], t: welch_t(a, b) }
+}
+
+/// t = (mean_a - mean_b) / sqrt(var_a / n_a + var_b / n_b); unlike Student's t it does not
+/// assume both classes have the same variance.
+pub fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+    let standard_error = (variance(a) / a.len() as f64 + variance(b) / b.len() as f64).sqrt();
+    if standard_error == 0.0 {
+        return 0.0; // Both classes took exactly the same time every time
+    }
+    (mean(a) - mean(b)) / standard_error
+}
+
+fn mean(xs: &[f64]) -> f64 {
+    xs.iter().sum::<f64>() / xs.len() as f64
+}
+
+fn variance(xs: &[f64]) -> f64 {
+    let m = mean(xs);
+    xs.iter().map(|x| (x - m).powi(2)).sum::<f64>() / (xs.len() as f64 - 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn welch_t_of_known_samples() {
+        // Means 2 and 5, variances 1 and 1, three samples each: t = -3 / sqrt(2 / 3)
+        let t = welch_t(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]);
+        assert!((t + 3.0 / (2.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert_eq!(welch_t(&[7.0, 7.0], &[7.0, 7.0]), 0.0);
+    }
+
+    #[test]
+    fn a_class_dependent_time_leaks() {
+        assert!(measure(1_000, |class| 100.0 + class as f64 + rand::thread_rng().gen_range(0.0..0.5)).leaks());
+        assert!(!measure(1_000, |_| 100.0).leaks());
+    }
+}
@@ -0,0 +1,87 @@
+// --- Candidate Ordering (Dictionary Attack, [cite: 256]) ---
+// Every rejected guess costs a full check, so the order in which DecryptByte4 tries the
+// 256 byte values decides the query count. Plaintexts of SSH/TLS records are mostly
+// text, so sorting the candidates by how often each byte occurs in typical traffic
+// finds the right one after a handful of checks instead of ~128 on average.
+
+// Training text: English prose plus the sort of credentials a login record carries
+const ENGLISH_SAMPLE: &str = include_str!("../../data/english_corpus.txt");
+const PASSWORD_SAMPLE: &str = include_str!("../../data/common_passwords.txt");
+
+#[derive(Clone, Copy, Debug)]
+pub enum CandidateOrder {
+    Sequential, // 0x00, 0x01, ..., 0xff as in the plain algorithm
+    Frequency,  // Most frequent bytes of the training text first
+}
+
+impl CandidateOrder {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CandidateOrder::Sequential => "Sequential",
+            CandidateOrder::Frequency => "Byte frequency",
+        }
+    }
+
+    /// All 256 byte values, in the order they should be tried.
+    pub fn candidates(&self, block_size: usize) -> Vec<u8> {
+        match self {
+            CandidateOrder::Sequential => (0..=255).collect(),
+            CandidateOrder::Frequency => frequency_order(block_size),
+        }
+    }
+}
+
+/// Bytes seen in the training text, most frequent first; then the padding values
+/// 0..block_size-1, which never appear in text but fill the end of the last block;
+/// then the rest of printable ASCII; then everything else in numeric order.
+fn frequency_order(block_size: usize) -> Vec<u8> {
+    let mut counts = [0usize; 256];
+    for byte in ENGLISH_SAMPLE.bytes().chain(PASSWORD_SAMPLE.bytes()) {
+        // The line breaks come from the sample files, not from the traffic
+        if byte != b'\n' && byte != b'\r' {
+            counts[byte as usize] += 1;
+        }
+    }
+
+    let mut seen: Vec<u8> = (0..=255).filter(|&b| counts[b as usize] > 0).collect();
+    seen.sort_by_key(|&b| std::cmp::Reverse(counts[b as usize])); // Stable: ties keep numeric order
+
+    let mut order = seen;
+    for padding in 0..block_size as u8 {
+        if !order.contains(&padding) {
+            order.push(padding);
+        }
+    }
+    for byte in (b' '..=b'~').chain(0..=255) {
+        if !order.contains(&byte) {
+            order.push(byte);
+        }
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_order_is_a_permutation_of_all_bytes() {
+        for order in [CandidateOrder::Sequential, CandidateOrder::Frequency] {
+            for block_size in [8, 16] {
+                let mut candidates = order.candidates(block_size);
+                candidates.sort_unstable();
+                assert_eq!(candidates, (0..=255).collect::<Vec<u8>>());
+            }
+        }
+    }
+
+    #[test]
+    fn text_comes_before_padding_and_binary() {
+        let candidates = CandidateOrder::Frequency.candidates(8);
+        let rank = |byte: u8| candidates.iter().position(|&c| c == byte).unwrap();
+        assert_eq!(candidates[..2], [b' ', b'e']);
+        // Seen in the samples < padding values < unseen printable ASCII < everything else
+        assert!(rank(b'e') < rank(0x00) && rank(0x07) < rank(b'Q'));
+        assert!(rank(b'Q') < rank(b'\n') && rank(b'Q') < rank(0x80));
+    }
+}
@@ -0,0 +1,480 @@
+use std::cell::Cell;
+use std::time::Duration;
+
+use rand::Rng;
+use sha1::digest::generic_array::GenericArray;
+
+use super::AttackError;
+use super::cipher::{CbcCipher, CipherKind};
+
+// --- Lucky Thirteen (AlFardan & Paterson 2013) ---
+// TLS uses MAC-then-encrypt: record = CBC( data || HMAC-SHA1(header || data) || padding ).
+// The server strips the padding, then the MAC, and recomputes the HMAC over what is left.
+// A record with bad padding is treated as if it had none, so only the MAC is stripped.
+// A forged record fails the MAC check either way and gets the same alert, but the HMAC
+// input is longer in the bad case, and when that crosses a 64-byte SHA-1 block boundary
+// the server runs one more compression function.
+//
+// With a 64-byte forged record (the 13-byte header and the data follow HMAC's 64-byte key block):
+//   bad padding    : 64 - 20           = 44 data bytes -> 64 + 13 + 44 = 121 -> 3 compressions
+//   0x00           : 64 - 20 - 1       = 43            -> 120           -> 3 compressions
+//   0x01 0x01 ...  : 64 - 20 - (k + 1) <= 42           -> <= 119        -> 2 compressions
+// so any valid padding of two or more bytes is one compression faster. (13 is the header length
+// that puts the boundary exactly there, hence the name.)
+
+const MAC_LEN: usize = 20;
+const HEADER_LEN: usize = 13; // seq (8) || type (1) || version (2) || length (2)
+const SHA1_BLOCK: usize = 64;
+const SHA1_INIT: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+const FORGED_BLOCKS: usize = 4;
+const BLOCK_SIZE: usize = 16; // AES-128
+
+// One compression is far below what thread::sleep can resolve, so this server does not sleep;
+// it reports how long the work would take on a simulated clock.
+const RECORD_OVERHEAD_NS: f64 = 5_000.0; // Decryption, parsing, sending the alert
+const COMPRESSION_NS: f64 = 1_000.0;
+const NOISE_SD_NS: f64 = 200.0;
+
+// Decision rule: one query to screen a candidate, then CONFIRM_SAMPLES more whose mean must lie
+// CONFIRM_SIGMAS standard errors below the bad-padding baseline.
+const SCREEN_SIGMAS: f64 = 2.0;
+const CONFIRM_SAMPLES: usize = 16;
+const CONFIRM_SIGMAS: f64 = 4.0;
+const PASSES: usize = 2; // The screen sometimes drops the right candidate; it gets a second chance
+
+/// TLS server with MAC-then-encrypt records (AES-128-CBC, HMAC-SHA1).
+pub struct TlsServer {
+    cipher: CbcCipher,
+    mac_key: [u8; MAC_LEN],
+    data: Vec<u8>, // What the victim's browser resends in every session, e.g. a cookie
+    queries: Cell<usize>,
+    constant_time: bool,
+}
+
+impl TlsServer {
+    /// `data` must be a whole number of AES blocks: `recover_block` treats every data block as
+    /// pure data, so a partial last block would come back with the first MAC bytes attached.
+    pub fn new(data: &[u8]) -> Self {
+        TlsServer::with_countermeasure(data, false)
+    }
+
+    /// The same server with the Lucky Thirteen countermeasure (see `verify_constant_time`).
+    pub fn hardened(data: &[u8]) -> Self {
+        TlsServer::with_countermeasure(data, true)
+    }
+
+    fn with_countermeasure(data: &[u8], constant_time: bool) -> Self {
+        assert!(
+            !data.is_empty() && data.len().is_multiple_of(BLOCK_SIZE),
+            "data must fill whole {}-byte blocks, got {} bytes",
+            BLOCK_SIZE,
+            data.len()
+        );
+        let mut rng = rand::thread_rng();
+        let mut mac_key = [0u8; MAC_LEN];
+        rng.fill(&mut mac_key);
+        TlsServer {
+            cipher: CbcCipher::random(CipherKind::Aes128, &mut rng),
+            mac_key,
+            data: data.to_vec(),
+            queries: Cell::new(0),
+            constant_time,
+        }
+    }
+
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+
+    /// Number of data blocks in the victim's record (MAC and padding excluded).
+    /// The data is block aligned, so none of them holds MAC bytes.
+    pub fn data_blocks(&self) -> usize {
+        self.data.len() / BLOCK_SIZE
+    }
+
+    /// The victim's record as seen on the wire: [IV, C_1, ..., C_n]
+    pub fn capture_record(&self) -> Vec<Vec<u8>> {
+        let mut record = self.data.clone();
+        record.extend(self.mac(&self.data));
+        // TLS padding: p + 1 bytes, all equal to p
+        let pad = BLOCK_SIZE - 1 - record.len() % BLOCK_SIZE;
+        record.extend(std::iter::repeat_n(pad as u8, pad + 1));
+
+        let mut iv = vec![0u8; BLOCK_SIZE];
+        rand::thread_rng().fill(iv.as_mut_slice());
+        let ciphertext = self.cipher.encrypt(&iv, &record);
+        std::iter::once(iv).chain(ciphertext.chunks(BLOCK_SIZE).map(<[u8]>::to_vec)).collect()
+    }
+
+    /// Processes a forged record (explicit IV first) and returns how long it took.
+    /// The answer is always bad_record_mac; only the time says anything.
+    pub fn query(&self, record: &[u8]) -> Duration {
+        self.queries.set(self.queries.get() + 1);
+        let (iv, body) = record.split_at(BLOCK_SIZE);
+        let plaintext = self.cipher.decrypt(iv, body);
+
+        let compressions = if self.constant_time {
+            self.verify_constant_time(&plaintext)
+        } else {
+            self.verify(&plaintext)
+        };
+
+        let noise = gaussian(&mut rand::thread_rng()) * NOISE_SD_NS;
+        let nanos = RECORD_OVERHEAD_NS + compressions as f64 * COMPRESSION_NS + noise;
+        Duration::from_nanos(nanos.max(0.0) as u64)
+    }
+
+    /// Strip padding and MAC, check the MAC; returns the number of compressions spent.
+    fn verify(&self, plaintext: &[u8]) -> usize {
+        let data_len = match padding_len(plaintext) {
+            Some(pad_len) => plaintext.len() - pad_len - MAC_LEN,
+            None => plaintext.len() - MAC_LEN, // Bad padding: carry on as if there were none
+        };
+        let (data, rest) = plaintext.split_at(data_len);
+        let mut compressions = 0;
+        let _mac_ok = self.counted_mac(data, &mut compressions)[..] == rest[..MAC_LEN];
+        compressions
+    }
+
+    /// The countermeasure: every record of a given length costs the same, whatever it decrypts to.
+    /// - the padding check looks at every byte that could be padding, without an early exit
+    /// - after the real MAC, dummy compressions bring the total up to what the longest possible
+    ///   data (no padding at all) would cost
+    /// - the MAC comparison does not stop at the first differing byte
+    fn verify_constant_time(&self, plaintext: &[u8]) -> usize {
+        let (pad_len, padding_ok) = padding_len_constant_time(plaintext);
+        let data_len = plaintext.len() - pad_len - MAC_LEN;
+        let (data, rest) = plaintext.split_at(data_len);
+        let mut compressions = 0;
+        let mac = self.counted_mac(data, &mut compressions);
+        let difference = mac.iter().zip(&rest[..MAC_LEN]).fold(0, |acc, (a, b)| acc | (a ^ b));
+        let _mac_ok = (difference == 0) & padding_ok;
+
+        // Dummy compressions, counted like the real ones, so a miscounted padding shows up in the time
+        let worst_case = hmac_compressions(plaintext.len() - MAC_LEN);
+        let mut state = SHA1_INIT;
+        while compressions < worst_case {
+            sha1::compress(&mut state, &[GenericArray::default()]);
+            compressions += 1;
+        }
+        std::hint::black_box(state);
+        compressions
+    }
+
+    fn mac(&self, data: &[u8]) -> Vec<u8> {
+        self.counted_mac(data, &mut 0).to_vec()
+    }
+
+    /// HMAC-SHA1(key, header || data) = H(K ^ opad || H(K ^ ipad || header || data)),
+    /// adding every compression it runs to `compressions`.
+    fn counted_mac(&self, data: &[u8], compressions: &mut usize) -> [u8; MAC_LEN] {
+        // seq 0, application_data (23), TLS 1.2, length
+        let mut header = [0u8; HEADER_LEN];
+        header[8] = 23;
+        header[9..11].copy_from_slice(&[3, 3]);
+        header[11..].copy_from_slice(&(data.len() as u16).to_be_bytes());
+
+        let key_block = |pad: u8| -> Vec<u8> {
+            let mut block = vec![pad; SHA1_BLOCK];
+            for (b, k) in block.iter_mut().zip(&self.mac_key) {
+                *b ^= k;
+            }
+            block
+        };
+        let inner = sha1([&key_block(0x36)[..], &header, data].concat(), compressions);
+        sha1([&key_block(0x5c)[..], &inner].concat(), compressions)
+    }
+}
+
+/// SHA-1 one compression at a time, so that the caller can count them.
+fn sha1(mut message: Vec<u8>, compressions: &mut usize) -> [u8; MAC_LEN] {
+    let bit_len = message.len() as u64 * 8;
+    let padded_len = sha1_compressions(message.len()) * SHA1_BLOCK;
+    message.push(0x80);
+    message.resize(padded_len - 8, 0);
+    message.extend(bit_len.to_be_bytes());
+
+    let mut state = SHA1_INIT;
+    for block in message.chunks(SHA1_BLOCK) {
+        sha1::compress(&mut state, &[GenericArray::clone_from_slice(block)]);
+        *compressions += 1;
+    }
+    let mut digest = [0u8; MAC_LEN];
+    for (bytes, word) in digest.chunks_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+/// Length of valid TLS padding (including the length byte), if there is room for it next to the MAC.
+fn padding_len(plaintext: &[u8]) -> Option<usize> {
+    let p = *plaintext.last()? as usize;
+    let pad_len = p + 1;
+    if pad_len + MAC_LEN > plaintext.len() {
+        return None;
+    }
+    plaintext[plaintext.len() - pad_len..].iter().all(|&b| b as usize == p).then_some(pad_len)
+}
+
+/// Same result as `padding_len` (bad padding counts as none), but every byte that could belong to
+/// the padding is read and folded into a mask instead of returning at the first mismatch.
+fn padding_len_constant_time(plaintext: &[u8]) -> (usize, bool) {
+    let n = plaintext.len();
+    let p = plaintext[n - 1] as usize;
+    let mut good = usize::from(p + 1 + MAC_LEN <= n);
+    for i in 1..=(n - MAC_LEN).min(256) {
+        let in_padding = usize::from(i <= p + 1);
+        let matches = usize::from(plaintext[n - i] as usize == p);
+        good &= (1 - in_padding) | matches;
+    }
+    (good * (p + 1), good == 1)
+}
+
+/// SHA-1 compressions for a message of `len` bytes: padding adds 0x80 and an 8-byte length.
+fn sha1_compressions(len: usize) -> usize {
+    (len + 9).div_ceil(SHA1_BLOCK)
+}
+
+/// HMAC = H(K ^ opad || H(K ^ ipad || header || data)); each key block costs one compression.
+fn hmac_compressions(data_len: usize) -> usize {
+    sha1_compressions(SHA1_BLOCK + HEADER_LEN + data_len) + sha1_compressions(SHA1_BLOCK + MAC_LEN)
+}
+
+/// Standard normal sample (Box-Muller)
+fn gaussian(rng: &mut impl Rng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen_range(0.0..1.0);
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Reply time of bad-padding records, learned from random forgeries (practically all of them are bad).
+pub struct Baseline {
+    pub mean: f64,
+    pub sd: f64,
+}
+
+impl Baseline {
+    pub fn calibrate(server: &TlsServer, samples: usize) -> Self {
+        let record = server.capture_record();
+        let times: Vec<f64> = (0..samples)
+            .map(|_| {
+                let mut delta = vec![0u8; BLOCK_SIZE];
+                rand::thread_rng().fill(delta.as_mut_slice());
+                nanos(server.query(&forge(&record, 0, &delta)))
+            })
+            .collect();
+        let n = times.len() as f64;
+        let mean = times.iter().sum::<f64>() / n;
+        let sd = (times.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+        Baseline { mean, sd }
+    }
+}
+
+fn nanos(duration: Duration) -> f64 {
+    duration.as_nanos() as f64
+}
+
+/// IV' || R_1 || R_2 || (C_{i-1} XOR delta) || C_i
+/// The last forged block decrypts to P_i XOR delta; the random blocks in front only fill the record
+/// up to the 64 bytes where the compression count flips.
+pub fn forge(record: &[Vec<u8>], block_index: usize, delta: &[u8]) -> Vec<u8> {
+    let mut forged = vec![0u8; BLOCK_SIZE * (FORGED_BLOCKS - 1)];
+    rand::thread_rng().fill(forged.as_mut_slice());
+    forged.extend(record[block_index].iter().zip(delta).map(|(c, d)| c ^ d));
+    forged.extend_from_slice(&record[block_index + 1]);
+    forged
+}
+
+/// Is the forged block's padding valid, i.e. was the server one compression faster?
+fn is_fast(server: &TlsServer, record: &[Vec<u8>], block_index: usize, delta: &[u8], baseline: &Baseline) -> bool {
+    let query = || nanos(server.query(&forge(record, block_index, delta)));
+    if query() > baseline.mean - SCREEN_SIGMAS * baseline.sd {
+        return false;
+    }
+    let mean = (0..CONFIRM_SAMPLES).map(|_| query()).sum::<f64>() / CONFIRM_SAMPLES as f64;
+    mean < baseline.mean - CONFIRM_SIGMAS * baseline.sd / (CONFIRM_SAMPLES as f64).sqrt()
+}
+
+/// Recovers data block `block_index` (0-based, below `server.data_blocks()`).
+/// The last two bytes have to be guessed together, since 0x01 0x01 is the shortest padding that
+/// saves a compression; `candidates` (most likely first) orders the pairs by combined rank.
+/// After that each byte is found alone by extending the padding to 0x02 0x02 0x02, 0x03 ..., and so on.
+/// `on_byte` sees every byte as soon as it is found, in recovery order (last byte first).
+pub fn recover_block(
+    server: &TlsServer,
+    block_index: usize,
+    candidates: &[u8],
+    baseline: &Baseline,
+    mut on_byte: impl FnMut(u8),
+) -> Result<Vec<u8>, AttackError> {
+    let record = server.capture_record();
+    let mut plaintext = vec![0u8; BLOCK_SIZE];
+
+    // Step 1: last two bytes, aiming for ... 0x01 0x01
+    let (x14, x15) = (0..PASSES)
+        .find_map(|_| last_two_bytes(server, &record, block_index, candidates, baseline))
+        .ok_or(AttackError::ByteNotFound { block_index, position: 14 })?;
+    plaintext[14] = x14;
+    plaintext[15] = x15;
+    on_byte(x15);
+    on_byte(x14);
+
+    // Step 2: the rest, one byte at a time with k + 1 bytes of padding value k
+    for k in 2..BLOCK_SIZE {
+        let position = BLOCK_SIZE - 1 - k;
+        let mut delta = vec![0u8; BLOCK_SIZE];
+        for j in position + 1..BLOCK_SIZE {
+            delta[j] = plaintext[j] ^ k as u8;
+        }
+        let byte = (0..PASSES)
+            .find_map(|_| {
+                candidates.iter().copied().find(|&c| {
+                    delta[position] = c ^ k as u8;
+                    is_fast(server, &record, block_index, &delta, baseline)
+                })
+            })
+            .ok_or(AttackError::ByteNotFound { block_index, position })?;
+        plaintext[position] = byte;
+        on_byte(byte);
+    }
+    Ok(plaintext)
+}
+
+/// One pass over the candidate pairs for the last two bytes, in order of combined rank.
+/// Finds nothing when there are no candidates.
+fn last_two_bytes(
+    server: &TlsServer,
+    record: &[Vec<u8>],
+    block_index: usize,
+    candidates: &[u8],
+    baseline: &Baseline,
+) -> Option<(u8, u8)> {
+    let last = candidates.len().checked_sub(1)?;
+    for rank_sum in 0..=2 * last {
+        for rank in rank_sum.saturating_sub(last)..=rank_sum.min(last) {
+            let (x14, x15) = (candidates[rank], candidates[rank_sum - rank]);
+            let mut delta = vec![0u8; BLOCK_SIZE];
+            delta[14] = x14 ^ 0x01;
+            delta[15] = x15 ^ 0x01;
+            if !is_fast(server, record, block_index, &delta, baseline) {
+                continue;
+            }
+            // ... 0x02 0x02 0x02 is fast too; if so, disturbing byte 13 makes it slow again
+            delta[13] ^= 0xff;
+            if is_fast(server, record, block_index, &delta, baseline) {
+                return Some((x14, x15));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use hmac::{Hmac, Mac};
+    use sha1::Sha1;
+
+    use super::*;
+    use crate::dudect;
+    use crate::guessing::CandidateOrder;
+
+    const COOKIE: &[u8] = b"Cookie: session=8c1f0e7a2b9d4c63";
+
+    #[test]
+    #[should_panic(expected = "whole 16-byte blocks")]
+    fn partial_data_blocks_are_rejected() {
+        TlsServer::new(b"Cookie: session=8c1f");
+    }
+
+    #[test]
+    fn padding_needs_room_next_to_the_mac() {
+        let mut plaintext = vec![0xaa; 64];
+        plaintext[62] = 0x01;
+        plaintext[63] = 0x01;
+        assert_eq!(padding_len(&plaintext), Some(2));
+        assert_eq!(padding_len_constant_time(&plaintext), (2, true));
+        plaintext[62] = 0x02;
+        assert_eq!(padding_len(&plaintext), None);
+        assert_eq!(padding_len_constant_time(&plaintext), (0, false));
+        plaintext[63] = 63; // 64 padding bytes would leave no room for the MAC
+        assert_eq!(padding_len(&plaintext), None);
+        assert_eq!(padding_len_constant_time(&plaintext), (0, false));
+    }
+
+    #[test]
+    fn no_candidates_finds_no_byte() {
+        let server = TlsServer::new(COOKIE);
+        let baseline = Baseline::calibrate(&server, 20);
+        let result = recover_block(&server, 0, &[], &baseline, |_| panic!("no byte can be found"));
+        assert!(matches!(result, Err(AttackError::ByteNotFound { block_index: 0, position: 14 })));
+    }
+
+    #[test]
+    fn two_byte_padding_saves_a_compression() {
+        let data_len = |pad_len: usize| FORGED_BLOCKS * BLOCK_SIZE - MAC_LEN - pad_len;
+        assert_eq!(hmac_compressions(data_len(0)), 5);
+        assert_eq!(hmac_compressions(data_len(1)), 5);
+        assert_eq!(hmac_compressions(data_len(2)), 4);
+    }
+
+    #[test]
+    fn counted_mac_is_hmac_sha1() {
+        let server = TlsServer::new(COOKIE);
+        for len in [0, 1, 43, 44, 55, 56, 200] {
+            let data = vec![0x42; len];
+            let mut header = [0u8; HEADER_LEN];
+            header[8] = 23;
+            header[9..11].copy_from_slice(&[3, 3]);
+            header[11..].copy_from_slice(&(len as u16).to_be_bytes());
+            let mut expected = Hmac::<Sha1>::new_from_slice(&server.mac_key).unwrap();
+            expected.update(&header);
+            expected.update(&data);
+
+            let mut compressions = 0;
+            assert_eq!(server.counted_mac(&data, &mut compressions)[..], expected.finalize().into_bytes()[..]);
+            assert_eq!(compressions, hmac_compressions(len));
+        }
+    }
+
+    #[test]
+    fn only_the_hardened_server_spends_the_same_work() {
+        let (server, hardened) = (TlsServer::new(COOKIE), TlsServer::hardened(COOKIE));
+        let mut good = vec![0xaa; FORGED_BLOCKS * BLOCK_SIZE];
+        good[62] = 0x01;
+        good[63] = 0x01;
+        let mut bad = good.clone();
+        bad[62] = 0x02;
+        assert_eq!(server.verify(&good) + 1, server.verify(&bad));
+        assert_eq!(hardened.verify_constant_time(&good), hardened.verify_constant_time(&bad));
+    }
+
+    #[test]
+    fn dudect_flags_only_the_leaky_server() {
+        for (server, leaky) in [(TlsServer::new(COOKIE), true), (TlsServer::hardened(COOKIE), false)] {
+            let record = server.capture_record();
+            let mut valid = vec![0u8; BLOCK_SIZE];
+            valid[14] = COOKIE[14] ^ 0x01;
+            valid[15] = COOKIE[15] ^ 0x01;
+            let report = dudect::measure(4_000, |class| {
+                let mut delta = valid.clone();
+                if class == 1 {
+                    rand::thread_rng().fill(delta.as_mut_slice());
+                }
+                nanos(server.query(&forge(&record, 0, &delta)))
+            });
+            assert_eq!(report.leaks(), leaky, "t = {}", report.t);
+        }
+    }
+
+    #[test]
+    fn every_data_block_is_recovered() {
+        let server = TlsServer::new(COOKIE);
+        assert_eq!(server.data_blocks(), 2);
+        let baseline = Baseline::calibrate(&server, 200);
+        let candidates = CandidateOrder::Frequency.candidates(BLOCK_SIZE);
+        let recovered: Vec<u8> = (0..server.data_blocks())
+            .flat_map(|block_index| recover_block(&server, block_index, &candidates, &baseline, |_| {}).unwrap())
+            .collect();
+        assert_eq!(recovered, COOKIE);
+    }
+}
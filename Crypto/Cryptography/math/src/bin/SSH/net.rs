@@ -0,0 +1,269 @@
+use std::cell::{Cell, RefCell};
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{MockOracle, PaddingOracle, ServerError};
+
+// --- Network Harness ---
+// The oracle behind a real socket on localhost, so the attacker's clock also sees the TCP stack,
+// thread wake-ups and scheduling, not only the delay the server adds itself.
+// Framing follows the SSH binary packet protocol (RFC 4253, Section 6) minus its own padding and MAC:
+//   uint32 length || byte message type || payload        (length counts the type byte and the payload)
+// A real server drops the connection after a bad record; this one keeps it open, since
+// reconnecting for every query would only add more of the same noise.
+
+const MSG_CAPTURE: u8 = 1; // Harness only: hand over a victim record, as if sniffed off the wire
+const MSG_RECORD: u8 = 2; // Forged IV || ciphertext -> MSG_DISCONNECT once the server is done with it
+const MSG_RECORD_EXPLICIT: u8 = 3; // Forged IV || ciphertext -> MSG_ALERT saying which check failed
+const MSG_DISCONNECT: u8 = 4; // "Corrupted MAC on input": the same for every failure
+const MSG_ALERT: u8 = 5; // 1: padding error, 2: MAC error
+
+const ALERT_PADDING: u8 = 1;
+const ALERT_MAC: u8 = 2;
+
+const MAX_PACKET: usize = 35_000; // RFC 4253: every implementation must handle at least this much
+
+/// Serves `oracle` on a free localhost port, one connection at a time, until the program exits.
+/// The flag is the ground truth of the last query (see `PaddingOracle::last_padding_valid`):
+/// it is shared in-process so the attack can still be scored, and never goes over the wire.
+pub fn serve(oracle: MockOracle) -> io::Result<(SocketAddr, Arc<AtomicBool>)> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    let truth = Arc::new(AtomicBool::new(false));
+    let server_truth = Arc::clone(&truth);
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            // A client hanging up (or sending garbage) ends its session; wait for the next one
+            let _ = handle(&oracle, stream, &server_truth);
+        }
+    });
+    Ok((addr, truth))
+}
+
+fn handle(oracle: &MockOracle, mut stream: TcpStream, truth: &AtomicBool) -> io::Result<()> {
+    stream.set_nodelay(true)?;
+    let block_size = oracle.block_size();
+    loop {
+        let (msg_type, payload) = read_packet(&mut stream)?;
+        match msg_type {
+            MSG_CAPTURE => {
+                let mut reply = vec![block_size as u8];
+                reply.extend(oracle.record().concat());
+                write_packet(&mut stream, MSG_CAPTURE, &reply)?;
+            }
+            MSG_RECORD | MSG_RECORD_EXPLICIT => {
+                if payload.len() < 2 * block_size || !payload.len().is_multiple_of(block_size) {
+                    return Err(invalid_data("record is not a whole number of blocks"));
+                }
+                let (iv, ciphertext) = payload.split_at(block_size);
+                if msg_type == MSG_RECORD {
+                    oracle.reply_time(iv, ciphertext);
+                    truth.store(oracle.last_padding_valid(), Ordering::SeqCst);
+                    write_packet(&mut stream, MSG_DISCONNECT, &[])?;
+                } else {
+                    let alert = match oracle.alert(iv, ciphertext) {
+                        ServerError::PaddingError => ALERT_PADDING,
+                        ServerError::MacError => ALERT_MAC,
+                    };
+                    truth.store(oracle.last_padding_valid(), Ordering::SeqCst);
+                    write_packet(&mut stream, MSG_ALERT, &[alert])?;
+                }
+            }
+            other => return Err(invalid_data(&format!("unexpected message type {}", other))),
+        }
+    }
+}
+
+/// The attacker's side: the same `PaddingOracle`, but every call is a round trip to the server.
+/// Reply times are measured here, from sending the record to receiving the answer.
+pub struct RemoteOracle {
+    stream: RefCell<TcpStream>,
+    block_size: usize,
+    queries: Cell<usize>,
+    truth: Arc<AtomicBool>,
+}
+
+impl RemoteOracle {
+    pub fn connect(addr: SocketAddr, truth: Arc<AtomicBool>) -> io::Result<Self> {
+        let mut stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?; // Send each record at once instead of waiting to batch (Nagle)
+
+        // The first captured record also tells the block size
+        write_packet(&mut stream, MSG_CAPTURE, &[])?;
+        let (_, reply) = read_packet(&mut stream)?;
+        let block_size = match reply.first() {
+            Some(&size) if size > 0 => size as usize,
+            _ => return Err(invalid_data("capture without a block size")),
+        };
+        Ok(RemoteOracle { stream: RefCell::new(stream), block_size, queries: Cell::new(0), truth })
+    }
+
+    fn exchange(&self, msg_type: u8, payload: &[u8]) -> io::Result<(u8, Vec<u8>)> {
+        let mut stream = self.stream.borrow_mut();
+        write_packet(&mut stream, msg_type, payload)?;
+        read_packet(&mut stream)
+    }
+
+    fn send_record(&self, msg_type: u8, iv: &[u8], ciphertext: &[u8]) -> io::Result<(u8, Vec<u8>)> {
+        self.queries.set(self.queries.get() + 1);
+        self.exchange(msg_type, &[iv, ciphertext].concat())
+    }
+}
+
+impl PaddingOracle for RemoteOracle {
+    fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    fn queries(&self) -> usize {
+        self.queries.get()
+    }
+
+    fn last_padding_valid(&self) -> bool {
+        self.truth.load(Ordering::SeqCst)
+    }
+
+    /// [block size, IV || y_1 || ... || y_n]: at least the IV and one block, all of the announced size
+    fn capture_record(&self) -> io::Result<Vec<Vec<u8>>> {
+        let (msg_type, reply) = self.exchange(MSG_CAPTURE, &[])?;
+        match reply.split_first() {
+            Some((&size, blocks))
+                if msg_type == MSG_CAPTURE
+                    && size as usize == self.block_size
+                    && blocks.len() >= 2 * self.block_size
+                    && blocks.len().is_multiple_of(self.block_size) =>
+            {
+                Ok(blocks.chunks(self.block_size).map(<[u8]>::to_vec).collect())
+            }
+            _ => Err(invalid_data("malformed capture")),
+        }
+    }
+
+    fn query_explicit(&self, iv: &[u8], ciphertext: &[u8]) -> io::Result<ServerError> {
+        match self.send_record(MSG_RECORD_EXPLICIT, iv, ciphertext)? {
+            (MSG_ALERT, alert) if alert == [ALERT_PADDING] => Ok(ServerError::PaddingError),
+            (MSG_ALERT, alert) if alert == [ALERT_MAC] => Ok(ServerError::MacError),
+            (msg_type, _) => Err(invalid_data(&format!("unexpected reply type {} to a record", msg_type))),
+        }
+    }
+
+    fn query(&self, iv: &[u8], ciphertext: &[u8]) -> io::Result<Duration> {
+        let start = Instant::now();
+        let (msg_type, _) = self.send_record(MSG_RECORD, iv, ciphertext)?;
+        let elapsed = start.elapsed();
+        if msg_type != MSG_DISCONNECT {
+            return Err(invalid_data(&format!("unexpected reply type {} to a record", msg_type)));
+        }
+        Ok(elapsed)
+    }
+}
+
+/// uint32 length || type || payload, in one write so the packet leaves in one segment
+fn write_packet(stream: &mut TcpStream, msg_type: u8, payload: &[u8]) -> io::Result<()> {
+    let mut packet = ((payload.len() + 1) as u32).to_be_bytes().to_vec();
+    packet.push(msg_type);
+    packet.extend_from_slice(payload);
+    stream.write_all(&packet)
+}
+
+fn read_packet(stream: &mut TcpStream) -> io::Result<(u8, Vec<u8>)> {
+    let mut length = [0u8; 4];
+    stream.read_exact(&mut length)?;
+    let length = u32::from_be_bytes(length) as usize;
+    if length == 0 || length > MAX_PACKET {
+        return Err(invalid_data("bad packet length"));
+    }
+    let mut packet = vec![0u8; length];
+    stream.read_exact(&mut packet)?;
+    let payload = packet.split_off(1);
+    Ok((packet[0], payload))
+}
+
+fn invalid_data(reason: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::CipherKind;
+    use crate::guessing::CandidateOrder;
+    use crate::sprt::AttackStats;
+    use crate::{AttackError, AttackMode, decrypt_message4};
+
+    const SECRET: &str = "login: alice / pw: PASSWRD1";
+
+    type Packet = (u8, Vec<u8>);
+
+    /// A server that answers every packet with `reply(msg_type)`, or hangs up on `None`.
+    fn fake_server(reply: fn(u8) -> Option<Packet>) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            while let Ok((msg_type, _)) = read_packet(&mut stream) {
+                match reply(msg_type) {
+                    Some((reply_type, reply)) => write_packet(&mut stream, reply_type, &reply).unwrap(),
+                    None => return, // Hang up
+                }
+            }
+        });
+        addr
+    }
+
+    fn connect(addr: SocketAddr) -> io::Result<RemoteOracle> {
+        RemoteOracle::connect(addr, Arc::new(AtomicBool::new(false)))
+    }
+
+    #[test]
+    fn boolean_attack_over_tcp() {
+        let (addr, truth) = serve(MockOracle::with_noise(SECRET, CipherKind::Des, 0)).unwrap();
+        let remote = RemoteOracle::connect(addr, truth).unwrap();
+        assert_eq!(remote.block_size(), 8);
+        let candidates = CandidateOrder::Frequency.candidates(8);
+        let mut stats = AttackStats::default();
+        let recovered = decrypt_message4(&remote, AttackMode::Boolean, &candidates, &mut stats).unwrap();
+        assert_eq!(recovered, SECRET.as_bytes());
+        assert_eq!(stats.false_positives + stats.false_negatives, 0);
+    }
+
+    #[test]
+    fn a_bad_block_size_is_refused() {
+        let addr = fake_server(|_| Some((MSG_CAPTURE, vec![0])));
+        assert_eq!(connect(addr).err().map(|error| error.kind()), Some(io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    fn a_truncated_capture_is_an_error() {
+        let addr = fake_server(|_| Some((MSG_CAPTURE, vec![8, 1, 2, 3])));
+        let remote = connect(addr).unwrap();
+        assert_eq!(remote.capture_record().unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn an_unexpected_reply_is_an_error() {
+        let addr = fake_server(|msg_type| match msg_type {
+            MSG_CAPTURE => Some((MSG_CAPTURE, vec![8; 17])),
+            _ => Some((MSG_CAPTURE, Vec::new())),
+        });
+        let remote = connect(addr).unwrap();
+        assert!(remote.query_explicit(&[0; 8], &[0; 8]).is_err());
+        assert!(remote.query(&[0; 8], &[0; 8]).is_err());
+    }
+
+    #[test]
+    fn a_dropped_connection_stops_the_attack() {
+        let addr = fake_server(|msg_type| match msg_type {
+            MSG_CAPTURE => Some((MSG_CAPTURE, vec![8; 17])),
+            _ => None,
+        });
+        let remote = connect(addr).unwrap();
+        let candidates = CandidateOrder::Sequential.candidates(8);
+        let error = decrypt_message4(&remote, AttackMode::Boolean, &candidates, &mut AttackStats::default()).unwrap_err();
+        assert!(matches!(error, AttackError::Oracle(_)), "{}", error);
+    }
+}
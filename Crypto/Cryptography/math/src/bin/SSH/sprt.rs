@@ -0,0 +1,131 @@
+// --- Sequential Probability Ratio Test configuration (Wald 1945) ---
+// H1: padding was valid (reply times follow D_R), H0: padding was invalid (D_W).
+// Keep adding ln( P(t|D_R) / P(t|D_W) ) for each reply and stop as soon as the sum leaves [ln B, ln A]:
+//   A = (1 - beta) / alpha    -> accept H1 above ln A
+//   B = beta / (1 - alpha)    -> accept H0 below ln B
+// Wald showed that these bounds keep the false-positive rate below ~alpha and the
+// false-negative rate below ~beta, whatever the two distributions are.
+
+#[derive(Clone, Copy, Debug)]
+pub struct SprtConfig {
+    pub false_positive: f64, // alpha: accept a wrong guess
+    pub false_negative: f64, // beta : reject the right guess
+    pub max_queries: usize,  // Give up (undecided) after this many replies
+}
+
+impl SprtConfig {
+    pub fn new(false_positive: f64, false_negative: f64, max_queries: usize) -> Self {
+        assert!(0.0 < false_positive && false_positive < 1.0, "alpha must be in (0, 1)");
+        assert!(0.0 < false_negative && false_negative < 1.0, "beta must be in (0, 1)");
+        SprtConfig { false_positive, false_negative, max_queries }
+    }
+
+    /// ln A: the log-likelihood ratio above which the guess is accepted
+    pub fn accept_threshold(&self) -> f64 {
+        ((1.0 - self.false_negative) / self.false_positive).ln()
+    }
+
+    /// ln B: the log-likelihood ratio below which the guess is rejected
+    pub fn reject_threshold(&self) -> f64 {
+        (self.false_negative / (1.0 - self.false_positive)).ln()
+    }
+
+    /// Ten times smaller error bounds and four times the query budget, for re-checking doubtful bytes
+    pub fn stricter(&self) -> Self {
+        SprtConfig::new(self.false_positive / 10.0, self.false_negative / 10.0, self.max_queries * 4)
+    }
+}
+
+impl Default for SprtConfig {
+    fn default() -> Self {
+        SprtConfig::new(1e-3, 1e-3, 100)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Decision {
+    Accept,
+    Reject,
+    Undecided, // Ran out of max_queries before either bound was crossed
+}
+
+/// What the attack cost and how often its individual checks were wrong.
+/// Errors are scored against the oracle's ground truth, which only a simulation can see.
+#[derive(Default)]
+pub struct AttackStats {
+    pub queries_per_byte: Vec<usize>, // One entry per attempt, so a byte that was taken back counts twice
+    pub checks: usize,
+    pub false_positives: usize, // Accepted, but the padding was invalid
+    pub false_negatives: usize, // Rejected, but the padding was valid
+    pub undecided: usize,
+    pub backtracks: usize, // Accepted bytes that failed re-verification and were taken back
+    pub verification_queries: usize, // Spent re-checking finished blocks and suffixes before backtracking
+}
+
+impl AttackStats {
+    pub fn record(&mut self, decision: Decision, padding_was_valid: bool) {
+        self.checks += 1;
+        match (decision, padding_was_valid) {
+            (Decision::Accept, false) => self.false_positives += 1,
+            (Decision::Reject, true) => self.false_negatives += 1,
+            (Decision::Undecided, _) => self.undecided += 1,
+            _ => {}
+        }
+    }
+
+    pub fn total_queries(&self) -> usize {
+        self.queries_per_byte.iter().sum::<usize>() + self.verification_queries
+    }
+
+    pub fn print_summary(&self) {
+        let bytes = self.queries_per_byte.len().max(1);
+        println!(
+            "Queries per byte: mean {:.1}, min {}, max {} | Checks {}: {} false positives, {} false negatives, {} undecided | {} backtracks",
+            self.total_queries() as f64 / bytes as f64,
+            self.queries_per_byte.iter().min().unwrap_or(&0),
+            self.queries_per_byte.iter().max().unwrap_or(&0),
+            self.checks,
+            self.false_positives,
+            self.false_negatives,
+            self.undecided,
+            self.backtracks
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thresholds_follow_the_error_bounds() {
+        let sprt = SprtConfig::new(0.01, 0.05, 10);
+        assert!((sprt.accept_threshold() - (0.95f64 / 0.01).ln()).abs() < 1e-12);
+        assert!((sprt.reject_threshold() - (0.05f64 / 0.99).ln()).abs() < 1e-12);
+        let stricter = sprt.stricter();
+        assert!(stricter.accept_threshold() > sprt.accept_threshold());
+        assert!(stricter.reject_threshold() < sprt.reject_threshold());
+        assert_eq!(stricter.max_queries, 40);
+    }
+
+    #[test]
+    #[should_panic(expected = "alpha must be in (0, 1)")]
+    fn a_zero_error_bound_is_rejected() {
+        SprtConfig::new(0.0, 0.1, 10);
+    }
+
+    #[test]
+    fn stats_score_decisions_against_the_truth() {
+        let mut stats = AttackStats::default();
+        stats.record(Decision::Accept, true);
+        stats.record(Decision::Accept, false);
+        stats.record(Decision::Reject, true);
+        stats.record(Decision::Reject, false);
+        stats.record(Decision::Undecided, true);
+        assert_eq!((stats.checks, stats.false_positives, stats.false_negatives, stats.undecided), (5, 1, 1, 1));
+
+        stats.queries_per_byte.extend([3, 4]);
+        stats.verification_queries = 2;
+        assert_eq!(stats.total_queries(), 9);
+    }
+}
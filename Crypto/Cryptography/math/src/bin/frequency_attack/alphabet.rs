@@ -0,0 +1,45 @@
+// [공용 도구] 영어 알파벳 26자만 암호화하는 도구들(치환, 비즈네르, 고전 암호 모음, 에니그마)이 같이 쓰는 글자 다루기
+// 알파벳만 A=0 ~ Z=25로 뽑고, 나머지 글자(공백, 문장부호, 한글 등)는 건드리지 않음
+
+// 알파벳만 골라서 0~25로 (대소문자 구분 없음). 받는 쪽에 맞춰 u8, usize, i32 등으로
+pub fn letters<T: From<u8>>(text: &str) -> Vec<T> {
+    text.bytes().filter(u8::is_ascii_alphabetic).map(|b| T::from(b.to_ascii_uppercase() - b'A')).collect()
+}
+
+// 글자(A=0 ~ Z=25)만 뽑아서 transform에 넘기고, 결과를 원래 글자 자리에 대소문자를 맞춰 되돌려 놓음
+// transform은 받은 만큼 돌려줘야 함 (글자 수가 그대로여야 나머지 문자가 제자리에 남음)
+pub fn map_letters(text: &str, transform: impl FnOnce(Vec<i32>) -> Vec<i32>) -> String {
+    let letters: Vec<i32> = letters(text);
+    let count = letters.len();
+    let transformed = transform(letters);
+    assert_eq!(transformed.len(), count, "글자 수가 바뀜");
+
+    let mut transformed = transformed.into_iter();
+    text.chars()
+        .map(|c| {
+            if c.is_ascii_alphabetic() {
+                let shifted = (b'A' + transformed.next().unwrap_or(0).rem_euclid(26) as u8) as char;
+                if c.is_ascii_lowercase() { shifted.to_ascii_lowercase() } else { shifted }
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_ignore_case_and_everything_else() {
+        assert_eq!(letters::<u8>("Ab, z! 한글 9"), [0, 1, 25]);
+        assert_eq!(letters::<usize>(""), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn map_letters_puts_letters_back_in_place() {
+        assert_eq!(map_letters("Hello, World!", |letters| letters.iter().map(|x| x + 1).collect()), "Ifmmp, Xpsme!");
+        assert_eq!(map_letters("zZ", |letters| letters.iter().map(|x| x - 27).collect()), "yY");
+    }
+}
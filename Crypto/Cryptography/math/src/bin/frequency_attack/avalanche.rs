@@ -0,0 +1,161 @@
+use rand::Rng;
+
+use crate::get_hash;
+
+// [도구 6] 쇄도 효과(Avalanche Effect) 측정기
+// 랜덤 입력 여러 개에 대해 입력 비트를 하나씩 뒤집어보고 출력 비트가 몇 개 바뀌는지 셈
+// - 좋은 해시: 어떤 입력 비트를 뒤집어도 출력 비트의 약 50%가 바뀜
+// - SAC(엄격한 쇄도 기준): 입력 비트 i를 뒤집을 때 출력 비트 j가 바뀔 확률이 모든 (i, j)에 대해 50%
+pub struct HashFunction {
+    pub name: &'static str,
+    pub hash: fn(&[u8]) -> Vec<u8>,
+}
+
+pub const HASH_FUNCTIONS: [HashFunction; 3] = [
+    HashFunction { name: "SHA-256", hash: sha256 },
+    HashFunction { name: "CRC32", hash: crc32 },
+    HashFunction { name: "djb2", hash: djb2 },
+];
+
+// 블록체인 해시 (get_hash의 hex 출력을 다시 바이트로)
+fn sha256(data: &[u8]) -> Vec<u8> {
+    hex::decode(get_hash(data)).expect("get_hash는 항상 hex")
+}
+
+// 오류 검출용 체크섬: 선형(XOR로만 섞음)이라 입력 비트 i가 바꾸는 출력 비트가 항상 똑같음
+fn crc32(data: &[u8]) -> Vec<u8> {
+    crc32fast::hash(data).to_be_bytes().to_vec()
+}
+
+// 해시테이블용 장난감 해시: hash * 33 + 바이트 -> 마지막 바이트는 아래쪽 비트밖에 못 건드림
+fn djb2(data: &[u8]) -> Vec<u8> {
+    let hash = data.iter().fold(5381u32, |hash, &b| hash.wrapping_mul(33).wrapping_add(b as u32));
+    hash.to_be_bytes().to_vec()
+}
+
+pub struct AvalancheReport {
+    pub name: &'static str,
+    pub output_bits: usize,
+    pub histogram: Vec<usize>, // histogram[d] = 출력 비트가 d개 바뀐 횟수
+    pub mean_ratio: f64,       // 평균 변화율 (이상적: 0.5)
+    pub std_ratio: f64,
+    pub sac_max_deviation: f64,  // SAC 행렬에서 0.5와 가장 먼 칸
+    pub sac_mean_deviation: f64, // SAC 행렬 전체의 |p - 0.5| 평균
+    pub samples: usize,
+}
+
+pub fn measure(function: &HashFunction, input_len: usize, samples: usize, rng: &mut impl Rng) -> AvalancheReport {
+    let input_bits = input_len * 8;
+    let output_bits = (function.hash)(&vec![0u8; input_len]).len() * 8;
+    let mut histogram = vec![0usize; output_bits + 1];
+    let mut flips = vec![vec![0u32; output_bits]; input_bits]; // flips[i][j] = 입력 i를 뒤집었을 때 출력 j가 바뀐 횟수
+
+    let mut input = vec![0u8; input_len];
+    for _ in 0..samples {
+        rng.fill(input.as_mut_slice());
+        let original = (function.hash)(&input);
+        for i in 0..input_bits {
+            input[i / 8] ^= 1 << (i % 8);
+            let flipped = (function.hash)(&input);
+            input[i / 8] ^= 1 << (i % 8);
+
+            let mut distance = 0;
+            for (j, count) in flips[i].iter_mut().enumerate() {
+                if (original[j / 8] ^ flipped[j / 8]) >> (j % 8) & 1 == 1 {
+                    *count += 1;
+                    distance += 1;
+                }
+            }
+            histogram[distance] += 1;
+        }
+    }
+
+    let trials = (samples * input_bits) as f64;
+    let mean = histogram.iter().enumerate().map(|(d, &n)| d as f64 * n as f64).sum::<f64>() / trials;
+    let variance = histogram.iter().enumerate().map(|(d, &n)| (d as f64 - mean).powi(2) * n as f64).sum::<f64>() / trials;
+    let deviations: Vec<f64> = flips
+        .iter()
+        .flatten()
+        .map(|&count| (count as f64 / samples as f64 - 0.5).abs())
+        .collect();
+
+    AvalancheReport {
+        name: function.name,
+        output_bits,
+        histogram,
+        mean_ratio: mean / output_bits as f64,
+        std_ratio: variance.sqrt() / output_bits as f64,
+        sac_max_deviation: deviations.iter().copied().fold(0.0, f64::max),
+        sac_mean_deviation: deviations.iter().sum::<f64>() / deviations.len() as f64,
+        samples,
+    }
+}
+
+impl AvalancheReport {
+    pub fn print_summary(&self) {
+        println!(
+            "{:<8} | 출력 {:>3}비트 | 평균 변화율 {:>6.2}% (±{:>5.2}%) | SAC 편차 평균 {:.3}, 최대 {:.3}",
+            self.name,
+            self.output_bits,
+            self.mean_ratio * 100.0,
+            self.std_ratio * 100.0,
+            self.sac_mean_deviation,
+            self.sac_max_deviation
+        );
+    }
+
+    // 바뀐 비트 수의 분포를 막대그래프로 (출력 비트 수를 20칸으로 묶음)
+    pub fn print_histogram(&self) {
+        println!("\n[{}] 바뀐 출력 비트 수 분포", self.name);
+        let bucket = self.output_bits.div_ceil(20);
+        let buckets: Vec<usize> = self.histogram.chunks(bucket).map(|chunk| chunk.iter().sum()).collect();
+        let peak = buckets.iter().copied().max().unwrap_or(1).max(1);
+        // 양 끝의 빈 구간은 생략
+        let first = buckets.iter().position(|&count| count > 0).unwrap_or(0);
+        let last = buckets.iter().rposition(|&count| count > 0).unwrap_or(0);
+        for (k, &count) in buckets.iter().enumerate().take(last + 1).skip(first) {
+            let bar = "#".repeat(count * 50 / peak);
+            println!("  {:>3}~{:<3} {}", k * bucket, ((k + 1) * bucket - 1).min(self.output_bits), bar);
+        }
+    }
+
+    // 표본이 유한해서 생기는 SAC 편차의 자연스러운 크기 (완벽한 해시라도 이 정도는 나옴)
+    pub fn sampling_noise(&self) -> f64 {
+        (0.25 / self.samples as f64).sqrt() * (2.0 / std::f64::consts::PI).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    fn report(name: &str) -> AvalancheReport {
+        let function = HASH_FUNCTIONS.iter().find(|function| function.name == name).unwrap();
+        measure(function, 8, 200, &mut StdRng::seed_from_u64(3))
+    }
+
+    #[test]
+    fn histogram_counts_every_flip() {
+        let report = report("SHA-256");
+        assert_eq!(report.output_bits, 256);
+        assert_eq!(report.histogram.iter().sum::<usize>(), 200 * 64);
+    }
+
+    #[test]
+    fn sha256_flips_half_the_bits() {
+        let report = report("SHA-256");
+        assert!((report.mean_ratio - 0.5).abs() < 0.01);
+        assert!(report.sac_mean_deviation < 2.0 * report.sampling_noise());
+    }
+
+    #[test]
+    fn crc32_is_linear() {
+        // 입력 비트마다 바뀌는 출력 비트가 항상 같아서 SAC 칸이 0% 아니면 100%
+        let report = report("CRC32");
+        assert_eq!(report.sac_max_deviation, 0.5);
+        assert_eq!(report.sac_mean_deviation, 0.5);
+    }
+}
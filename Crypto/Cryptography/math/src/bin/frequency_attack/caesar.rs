@@ -0,0 +1,85 @@
+use crate::frequency::{Language, chi_squared};
+use crate::hangul;
+
+// [도구 3] 시저 암호 자동 해독기
+// 키가 알파벳 크기(영어 26, 한글 자모 24)만큼밖에 없으니 전부 풀어보고
+// 그중 글자 빈도가 그 언어와 가장 비슷한(카이제곱이 가장 작은) 후보를 고름
+pub struct CaesarGuess {
+    pub shift: usize,
+    pub plaintext: String,
+    pub score: f64,
+}
+
+// 알파벳 안에서 shift칸 뒤로 밀기 (영어는 대소문자 유지, 한글 음절은 자모로 풀어서 밀고 다시 음절로 묶기)
+// 영어 알파벳으로 밀 때는 한글을 건드리지 않음 (풀었다 묶으면 '아까' -> '악가'처럼 바뀔 수 있음)
+pub fn rotate(text: &str, shift: usize, language: &Language) -> String {
+    if language.letters.iter().all(char::is_ascii) {
+        return shift_letters(text, shift, language);
+    }
+    hangul::compose(&shift_letters(&hangul::decompose(text), shift, language))
+}
+
+fn shift_letters(text: &str, shift: usize, language: &Language) -> String {
+    text.chars().map(|c| shift_char(c, shift, language)).collect()
+}
+
+// 글자 하나만 shift칸 밀기 (비즈네르는 글자마다 shift가 다른 시저)
+pub fn shift_char(c: char, shift: usize, language: &Language) -> char {
+    match language.index_of(c) {
+        Some(index) => {
+            let shifted = language.letters[(index + shift) % language.letters.len()];
+            if c.is_ascii_lowercase() { shifted.to_ascii_lowercase() } else { shifted }
+        }
+        None => c, // 공백이나 특수문자는 그대로 둠
+    }
+}
+
+// 모든 키를 시도해서 점수 좋은 순으로 정렬
+pub fn break_caesar(ciphertext: &str, language: &Language) -> Vec<CaesarGuess> {
+    let size = language.letters.len();
+    let mut guesses: Vec<CaesarGuess> = (0..size)
+        .map(|shift| {
+            let plaintext = rotate(ciphertext, size - shift, language);
+            let score = chi_squared(&language.normalize(&plaintext), language);
+            CaesarGuess { shift, plaintext, score }
+        })
+        .collect();
+    guesses.sort_by(|a, b| a.score.total_cmp(&b.score));
+    guesses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frequency::{ENGLISH, KOREAN_JAMO};
+
+    #[test]
+    fn rotate_keeps_case_and_punctuation() {
+        assert_eq!(rotate("Hello, World! xyz", 3, &ENGLISH), "Khoor, Zruog! abc");
+        assert_eq!(rotate(&rotate("Hello, World!", 3, &ENGLISH), 23, &ENGLISH), "Hello, World!");
+        assert_eq!(rotate("아까 ㄱㅏ", 3, &ENGLISH), "아까 ㄱㅏ");
+    }
+
+    #[test]
+    fn hangul_comes_back_as_syllables() {
+        let text = "비트코인은 누구나 장부를 복사해서 들고 있기 때문에 한 사람이 마음대로 기록을 고칠 수 없다.";
+        let encrypted = rotate(text, 5, &KOREAN_JAMO);
+        assert_ne!(encrypted, text);
+        assert_eq!(rotate(&encrypted, 19, &KOREAN_JAMO), text);
+    }
+
+    #[test]
+    fn finds_the_shift_of_english_text() {
+        let text = "Blockchain security does not come from hiding the algorithm. \
+            It comes from keys that are too large to guess and hashes that leak no pattern at all.";
+        let guesses = break_caesar(&rotate(text, 11, &ENGLISH), &ENGLISH);
+        assert_eq!(guesses[0].shift, 11);
+        assert_eq!(guesses[0].plaintext, text);
+    }
+
+    #[test]
+    fn empty_ciphertext_has_no_nan_scores() {
+        assert!(break_caesar("", &ENGLISH).iter().all(|guess| !guess.score.is_nan()));
+        assert!(break_caesar("123 !?", &KOREAN_JAMO).iter().all(|guess| !guess.score.is_nan()));
+    }
+}
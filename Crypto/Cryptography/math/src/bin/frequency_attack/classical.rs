@@ -0,0 +1,397 @@
+use std::fmt;
+
+use crate::alphabet::map_letters;
+use crate::caesar::rotate;
+use crate::frequency::ENGLISH;
+use crate::vigenere;
+
+// [고전 암호 모음] 모든 고전 암호를 같은 모양(암호화/복호화)으로 다루기 위한 트레이트
+// 시저/비즈네르처럼 알파벳만 암호화하고 공백/문장부호/한글 등은 그대로 둠, 대소문자도 그 자리 그대로
+// 글자 수도 바뀌지 않게 만들어서 (채우기 글자 X 없음) 어떤 문자열이든 decrypt(encrypt(x)) == normalize(x)
+pub trait ClassicalCipher {
+    fn name(&self) -> String;
+    fn encrypt(&self, plaintext: &str) -> String;
+    fn decrypt(&self, ciphertext: &str) -> String;
+
+    // 암호화하면 되돌릴 수 없게 뭉개지는 글자를 미리 바꾼 평문 (플레이페어의 J -> I 말고는 그대로)
+    fn normalize(&self, plaintext: &str) -> String {
+        plaintext.to_string()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum KeyError {
+    NotInvertible(i32), // 아핀의 a나 힐 행렬식이 26과 서로소가 아님 -> 복호화 불가
+    EmptyKeyword,       // 키워드에 쓸 글자가 없음
+    BadRotor(usize),    // 에니그마: 없는 로터 번호이거나 같은 로터를 두 번 씀
+    BadSetting(String), // 에니그마: 링/위치가 3글자가 아님
+    BadPlug(String),    // 에니그마: 플러그가 서로 다른 두 글자가 아니거나 이미 꽂힌 글자
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyError::NotInvertible(value) => write!(f, "{}은(는) 26과 서로소가 아니라서 역원이 없습니다", value),
+            KeyError::EmptyKeyword => write!(f, "키워드가 비어 있습니다"),
+            KeyError::BadRotor(rotor) => write!(f, "로터 {}은(는) 없거나 이미 다른 자리에 있습니다 (0~4 = I~V)", rotor),
+            KeyError::BadSetting(setting) => write!(f, "링/위치 설정 '{}'은(는) 알파벳 3글자가 아닙니다", setting),
+            KeyError::BadPlug(plug) => write!(f, "플러그 '{}'은(는) 서로 다른 빈 글자 두 개가 아닙니다", plug),
+        }
+    }
+}
+
+// a * x = 1 (mod 26)인 x
+fn inverse_mod_26(a: i32) -> Result<i32, KeyError> {
+    (1..26).find(|&x| (a * x).rem_euclid(26) == 1).ok_or(KeyError::NotInvertible(a))
+}
+
+// 1. 시저: 모든 글자를 shift칸 밀기
+pub struct Caesar {
+    pub shift: u8,
+}
+
+impl ClassicalCipher for Caesar {
+    fn name(&self) -> String {
+        format!("Caesar(+{})", self.shift)
+    }
+
+    fn encrypt(&self, plaintext: &str) -> String {
+        rotate(plaintext, self.shift as usize % 26, &ENGLISH)
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> String {
+        rotate(ciphertext, 26 - self.shift as usize % 26, &ENGLISH)
+    }
+}
+
+// 2. 아핀: E(x) = a*x + b (mod 26), a는 26과 서로소여야 역원이 있음
+pub struct Affine {
+    a: i32,
+    b: i32,
+    a_inverse: i32,
+}
+
+impl Affine {
+    pub fn new(a: i32, b: i32) -> Result<Self, KeyError> {
+        // 키도 mod 26으로 줄여 둬야 a * x가 i32를 넘지 않음
+        let (a, b) = (a.rem_euclid(26), b.rem_euclid(26));
+        let a_inverse = inverse_mod_26(a)?;
+        Ok(Affine { a, b, a_inverse })
+    }
+}
+
+impl ClassicalCipher for Affine {
+    fn name(&self) -> String {
+        format!("Affine({}x+{})", self.a, self.b)
+    }
+
+    fn encrypt(&self, plaintext: &str) -> String {
+        map_letters(plaintext, |letters| letters.iter().map(|&x| self.a * x + self.b).collect())
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> String {
+        map_letters(ciphertext, |letters| letters.iter().map(|&y| self.a_inverse * (y - self.b)).collect())
+    }
+}
+
+// 3. 비즈네르: 키 글자마다 다른 시저
+pub struct Vigenere {
+    key: String,
+}
+
+impl Vigenere {
+    pub fn new(key: &str) -> Result<Self, KeyError> {
+        vigenere::key_shifts(key).map_err(|_| KeyError::EmptyKeyword)?;
+        Ok(Vigenere { key: key.to_string() })
+    }
+}
+
+impl ClassicalCipher for Vigenere {
+    fn name(&self) -> String {
+        format!("Vigenere({})", self.key)
+    }
+
+    fn encrypt(&self, plaintext: &str) -> String {
+        vigenere::encrypt(plaintext, &self.key).expect("new에서 키를 검사함")
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> String {
+        vigenere::decrypt(ciphertext, &self.key).expect("new에서 키를 검사함")
+    }
+}
+
+// 4. 플레이페어: 5x5 표에서 두 글자씩 암호화 (단일 글자 빈도 분석이 안 통함)
+// 교과서 방식은 같은 글자 쌍 사이와 홀수 길이 끝에 X를 끼우는데, 그러면 복호화할 때 진짜 X와 구분이 안 됨
+// 여기서는 글자 수를 그대로 두는 변형을 씀: 같은 글자 쌍은 같은 행 규칙으로, 짝 없는 마지막 글자는 오른쪽으로 한 칸
+// (암호문의 같은 글자 쌍은 같은 글자 쌍에서만 나오므로 복호화가 하나로 정해짐)
+const LETTER_I: i32 = 8;
+const LETTER_J: i32 = 9;
+
+pub struct Playfair {
+    keyword: String,
+    square: Vec<i32>, // 25칸, 행 우선 (J는 I와 같은 칸)
+}
+
+impl Playfair {
+    pub fn new(keyword: &str) -> Self {
+        let mut square = Vec::new();
+        let keyword_letters = keyword.bytes().filter(u8::is_ascii_alphabetic).map(|b| (b.to_ascii_uppercase() - b'A') as i32);
+        for x in keyword_letters.chain(0..26) {
+            let x = if x == LETTER_J { LETTER_I } else { x };
+            if !square.contains(&x) {
+                square.push(x);
+            }
+        }
+        Playfair { keyword: keyword.to_string(), square }
+    }
+
+    fn position(&self, x: i32) -> (usize, usize) {
+        let x = if x == LETTER_J { LETTER_I } else { x };
+        let i = self.square.iter().position(|&s| s == x).expect("J 말고는 25글자가 모두 표에 있음");
+        (i / 5, i % 5)
+    }
+
+    fn at(&self, row: usize, col: usize) -> i32 {
+        self.square[(row % 5) * 5 + col % 5]
+    }
+
+    // 같은 행(같은 글자 포함) -> 오른쪽(복호화는 왼쪽), 같은 열 -> 아래(위), 나머지 -> 직사각형의 반대 모서리
+    fn transform_pairs(&self, letters: &[i32], step: usize) -> Vec<i32> {
+        let mut out = Vec::with_capacity(letters.len());
+        for pair in letters.chunks(2) {
+            let (r1, c1) = self.position(pair[0]);
+            let Some(&second) = pair.get(1) else {
+                out.push(self.at(r1, c1 + step));
+                break;
+            };
+            let (r2, c2) = self.position(second);
+            if r1 == r2 {
+                out.push(self.at(r1, c1 + step));
+                out.push(self.at(r2, c2 + step));
+            } else if c1 == c2 {
+                out.push(self.at(r1 + step, c1));
+                out.push(self.at(r2 + step, c2));
+            } else {
+                out.push(self.at(r1, c2));
+                out.push(self.at(r2, c1));
+            }
+        }
+        out
+    }
+}
+
+impl ClassicalCipher for Playfair {
+    fn name(&self) -> String {
+        format!("Playfair({})", self.keyword)
+    }
+
+    fn encrypt(&self, plaintext: &str) -> String {
+        map_letters(plaintext, |letters| self.transform_pairs(&letters, 1))
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> String {
+        map_letters(ciphertext, |letters| self.transform_pairs(&letters, 4))
+    }
+
+    // 표가 25칸이라 J는 I로 합쳐짐
+    fn normalize(&self, plaintext: &str) -> String {
+        plaintext.replace('J', "I").replace('j', "i")
+    }
+}
+
+// 5. 힐: 두 글자를 벡터로 보고 2x2 행렬을 곱함 (mod 26), 행렬식이 26과 서로소여야 복호화 가능
+// 홀수 길이의 마지막 글자는 짝이 없어서 행렬의 대각합만큼 시저로 밀기 (X를 채우면 진짜 X와 구분이 안 됨)
+pub struct Hill {
+    key: [[i32; 2]; 2],
+    inverse: [[i32; 2]; 2],
+}
+
+impl Hill {
+    pub fn new(key: [[i32; 2]; 2]) -> Result<Self, KeyError> {
+        let key = key.map(|row| row.map(|x| x.rem_euclid(26)));
+        let det = (key[0][0] * key[1][1] - key[0][1] * key[1][0]).rem_euclid(26);
+        let det_inverse = inverse_mod_26(det)?;
+        let inverse = [
+            [(det_inverse * key[1][1]).rem_euclid(26), (-det_inverse * key[0][1]).rem_euclid(26)],
+            [(-det_inverse * key[1][0]).rem_euclid(26), (det_inverse * key[0][0]).rem_euclid(26)],
+        ];
+        Ok(Hill { key, inverse })
+    }
+
+    fn multiply(matrix: &[[i32; 2]; 2], letters: &[i32], lone_shift: i32) -> Vec<i32> {
+        letters
+            .chunks(2)
+            .flat_map(|pair| match *pair {
+                [x, y] => vec![matrix[0][0] * x + matrix[0][1] * y, matrix[1][0] * x + matrix[1][1] * y],
+                _ => vec![pair[0] + lone_shift],
+            })
+            .collect()
+    }
+
+    fn trace(&self) -> i32 {
+        self.key[0][0] + self.key[1][1]
+    }
+}
+
+impl ClassicalCipher for Hill {
+    fn name(&self) -> String {
+        format!("Hill({:?})", self.key)
+    }
+
+    fn encrypt(&self, plaintext: &str) -> String {
+        map_letters(plaintext, |letters| Self::multiply(&self.key, &letters, self.trace()))
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> String {
+        map_letters(ciphertext, |letters| Self::multiply(&self.inverse, &letters, -self.trace()))
+    }
+}
+
+// 6. 열 전치: 키 길이만큼 줄을 맞춰 쓰고, 키 글자의 알파벳 순서대로 세로로 읽기
+// 글자는 그대로고 자리만 바뀜 -> 글자 빈도는 평문과 똑같음 (빈도 분석 보고서가 '전치'로 분류)
+// 알파벳만 옮기고, 대소문자와 나머지 문자는 원래 자리에 남김
+pub struct ColumnarTransposition {
+    keyword: String,
+    order: Vec<usize>, // 읽을 열 번호 순서
+}
+
+impl ColumnarTransposition {
+    pub fn new(keyword: &str) -> Result<Self, KeyError> {
+        let keyword_chars: Vec<char> = keyword.chars().collect();
+        if keyword_chars.is_empty() {
+            return Err(KeyError::EmptyKeyword);
+        }
+        let mut order: Vec<usize> = (0..keyword_chars.len()).collect();
+        order.sort_by_key(|&i| keyword_chars[i]); // 같은 글자는 왼쪽 먼저 (안정 정렬)
+        Ok(ColumnarTransposition { keyword: keyword.to_string(), order })
+    }
+
+    // 마지막 줄이 덜 차는 경우: 앞쪽 열들만 한 글자씩 더 가짐
+    fn column_len(&self, text_len: usize, column: usize) -> usize {
+        let width = self.order.len();
+        text_len / width + usize::from(column < text_len % width)
+    }
+}
+
+impl ClassicalCipher for ColumnarTransposition {
+    fn name(&self) -> String {
+        format!("Columnar({})", self.keyword)
+    }
+
+    fn encrypt(&self, plaintext: &str) -> String {
+        let width = self.order.len();
+        map_letters(plaintext, |letters| {
+            self.order
+                .iter()
+                .flat_map(|&column| letters.iter().copied().skip(column).step_by(width))
+                .collect()
+        })
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> String {
+        let width = self.order.len();
+        map_letters(ciphertext, |letters| {
+            let mut columns = vec![Vec::new(); width];
+            let mut start = 0;
+            for &column in &self.order {
+                let len = self.column_len(letters.len(), column);
+                columns[column] = letters[start..start + len].to_vec();
+                start += len;
+            }
+            (0..letters.len()).map(|i| columns[i % width][i / width]).collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    fn ciphers() -> Vec<Box<dyn ClassicalCipher>> {
+        vec![
+            Box::new(Caesar { shift: 3 }),
+            Box::new(Affine::new(5, 8).unwrap()),
+            Box::new(Vigenere::new("LEMON").unwrap()),
+            Box::new(Playfair::new("MONARCHY")),
+            Box::new(Hill::new([[3, 3], [2, 5]]).unwrap()),
+            Box::new(ColumnarTransposition::new("ZEBRAS").unwrap()),
+        ]
+    }
+
+    // 아무 문자열: 절반은 출력 가능한 ASCII(대소문자, 숫자, 공백, 문장부호), 절반은 아무 유니코드 글자
+    fn arbitrary_text(rng: &mut impl Rng) -> String {
+        let len = rng.gen_range(0..=60);
+        (0..len)
+            .map(|_| if rng.gen_bool(0.5) { rng.gen_range(' '..='~') } else { rng.r#gen::<char>() })
+            .collect()
+    }
+
+    #[test]
+    fn round_trip_on_arbitrary_text() {
+        let mut rng = StdRng::seed_from_u64(36);
+        for cipher in ciphers() {
+            for _ in 0..500 {
+                let plaintext = arbitrary_text(&mut rng);
+                let round_trip = cipher.decrypt(&cipher.encrypt(&plaintext));
+                assert_eq!(round_trip, cipher.normalize(&plaintext), "{}: decrypt(encrypt(x)) != x", cipher.name());
+            }
+        }
+    }
+
+    #[test]
+    fn non_letters_and_case_stay_in_place() {
+        let plaintext = "Attack at dawn, 한글 123!";
+        for cipher in ciphers() {
+            let encrypted = cipher.encrypt(plaintext);
+            for (p, c) in plaintext.chars().zip(encrypted.chars()) {
+                if p.is_ascii_alphabetic() {
+                    assert_eq!(p.is_ascii_uppercase(), c.is_ascii_uppercase(), "{}: {}", cipher.name(), encrypted);
+                } else {
+                    assert_eq!(p, c, "{}: {}", cipher.name(), encrypted);
+                }
+            }
+            assert_eq!(encrypted.chars().count(), plaintext.chars().count());
+        }
+    }
+
+    #[test]
+    fn textbook_vectors() {
+        assert_eq!(Affine::new(5, 8).unwrap().encrypt("AFFINECIPHER"), "IHHWVCSWFRCP");
+        assert_eq!(Hill::new([[3, 3], [2, 5]]).unwrap().encrypt("HELP"), "HIAT");
+        assert_eq!(ColumnarTransposition::new("ZEBRAS").unwrap().encrypt("WEAREDISCOVEREDFLEEATONCE"), "EVLNACDTESEAROFODEECWIREE");
+        // 같은 글자 쌍이 없는 짝수 길이면 교과서 플레이페어와 같음
+        assert_eq!(Playfair::new("PLAYFAIREXAMPLE").encrypt("HIDETHEGOLDINTHETREXESTUMP"), "BMODZBXDNABEKUDMUIXMMOUVIF");
+    }
+
+    #[test]
+    fn real_x_survives_playfair_and_hill() {
+        let playfair = Playfair::new("MONARCHY");
+        let hill = Hill::new([[3, 3], [2, 5]]).unwrap();
+        for text in ["BOX", "AXA", "EXXE", "BALLOON", "X"] {
+            assert_eq!(playfair.decrypt(&playfair.encrypt(text)), text);
+            assert_eq!(hill.decrypt(&hill.encrypt(text)), text);
+        }
+    }
+
+    #[test]
+    fn bad_keys_are_rejected() {
+        assert_eq!(Affine::new(13, 1).err(), Some(KeyError::NotInvertible(13)));
+        assert_eq!(Hill::new([[2, 4], [6, 8]]).err(), Some(KeyError::NotInvertible(18)));
+        assert_eq!(ColumnarTransposition::new("").err(), Some(KeyError::EmptyKeyword));
+        assert_eq!(Vigenere::new("123").err(), Some(KeyError::EmptyKeyword));
+    }
+
+    #[test]
+    fn huge_keys_are_reduced_mod_26() {
+        // i32::MAX = 23, i32::MIN + 1 = 3 (mod 26)
+        let text = "Attack at dawn";
+        assert_eq!(Affine::new(i32::MAX, i32::MIN + 1).unwrap().encrypt(text), Affine::new(23, 3).unwrap().encrypt(text));
+        assert_eq!(Affine::new(-3, -18).unwrap().encrypt(text), Affine::new(23, 8).unwrap().encrypt(text));
+        let hill = Hill::new([[i32::MAX, 3], [2, i32::MAX - 18]]).unwrap();
+        assert_eq!(hill.name(), "Hill([[23, 3], [2, 5]])");
+        assert_eq!(hill.decrypt(&hill.encrypt(text)), text);
+    }
+}
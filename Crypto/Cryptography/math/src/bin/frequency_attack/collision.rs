@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+// [도구 8] 잘린(truncated) SHA-256으로 해시 공격 실험
+// SHA-256 출력의 앞 n비트만 쓰는 "작은 해시"를 만들어서 n을 키워가며 시도 횟수를 잼
+// - 충돌(아무 두 입력이 같은 해시): 생일 역설 때문에 약 2^(n/2)번
+// - 역상(정해진 해시값을 만드는 입력): 약 2^n번
+// 진짜 SHA-256(n = 256)이면 충돌도 2^128번이라 우주가 끝날 때까지 못 찾음
+pub const MIN_BITS: u32 = 4; // 1~2비트 해시는 x -> h(x)가 순열이 되기 쉬워서 로 공격에 꼬리가 안 생김
+const MAX_RHO_RESTARTS: usize = 64;
+
+pub struct TruncatedHash {
+    pub bits: u32,
+    salt: [u8; 8], // 실험마다 다른 해시 함수가 되도록 섞는 값
+}
+
+impl TruncatedHash {
+    pub fn new(bits: u32, rng: &mut impl Rng) -> Self {
+        assert!((MIN_BITS..=64).contains(&bits), "n은 {}~64비트", MIN_BITS);
+        let mut salt = [0u8; 8];
+        rng.fill(&mut salt);
+        TruncatedHash { bits, salt }
+    }
+
+    pub fn hash(&self, input: u64) -> u64 {
+        let mut hasher = Sha256::new();
+        hasher.update(self.salt);
+        hasher.update(input.to_le_bytes());
+        let digest = hasher.finalize();
+        let prefix = u64::from_be_bytes(digest[..8].try_into().unwrap());
+        prefix >> (64 - self.bits)
+    }
+
+    // 가장 큰 해시값 2^n - 1 (n = 64일 때 1 << 64는 넘쳐서 오른쪽 시프트로 만듦)
+    pub fn max_output(&self) -> u64 {
+        u64::MAX >> (64 - self.bits)
+    }
+}
+
+// 1. 생일 공격: 해시값을 표에 적어두다가 이미 있는 값이 나오면 충돌 (메모리 2^(n/2)개 필요)
+pub fn birthday_collision(h: &TruncatedHash) -> (u64, u64, u64) {
+    let mut seen: HashMap<u64, u64> = HashMap::new();
+    for (attempts, input) in (0u64..).enumerate() {
+        let value = h.hash(input);
+        if let Some(&earlier) = seen.get(&value) {
+            return (earlier, input, attempts as u64 + 1);
+        }
+        seen.insert(value, input);
+    }
+    unreachable!()
+}
+
+// 2. 폴라드 로 + 플로이드 순환 찾기: x -> h(x) 를 계속 따라가면 결국 고리(ρ 모양)에 빠짐
+//    고리로 들어가는 입구에서 서로 다른 두 값이 같은 다음 값을 가리킴 = 충돌 (메모리 거의 0)
+//    출발점마다 이미 고리 위라서 꼬리가 없으면 MAX_RHO_RESTARTS번 다시 시도하고 포기 (None)
+pub fn rho_collision(h: &TruncatedHash, rng: &mut impl Rng) -> Option<(u64, u64, u64)> {
+    let mut attempts = 0u64;
+    for _ in 0..MAX_RHO_RESTARTS {
+        let start = rng.gen_range(0..=h.max_output());
+        let mut f = |x: u64| {
+            attempts += 1;
+            h.hash(x)
+        };
+
+        // 거북이는 한 칸, 토끼는 두 칸씩 -> 고리 안에서 만남
+        let mut tortoise = f(start);
+        let mut hare = f(start);
+        hare = f(hare);
+        while tortoise != hare {
+            tortoise = f(tortoise);
+            hare = f(hare);
+            hare = f(hare);
+        }
+
+        // 거북이를 출발점으로 돌려보내고 둘 다 한 칸씩 -> 고리 입구에서 만남
+        tortoise = start;
+        if tortoise == hare {
+            continue; // 출발점이 이미 고리 위 (입구 앞에 꼬리가 없음) -> 다른 출발점으로
+        }
+        loop {
+            let (next_tortoise, next_hare) = (f(tortoise), f(hare));
+            if next_tortoise == next_hare {
+                return Some((tortoise, hare, attempts));
+            }
+            tortoise = next_tortoise;
+            hare = next_hare;
+        }
+    }
+    None
+}
+
+// 3. 역상 공격: 목표 해시값이 나올 때까지 입력을 하나씩 시도
+pub fn brute_force_preimage(h: &TruncatedHash, target: u64) -> (u64, u64) {
+    for (attempts, input) in (0u64..).enumerate() {
+        if h.hash(input) == target {
+            return (input, attempts as u64 + 1);
+        }
+    }
+    unreachable!()
+}
+
+// n비트마다 각 공격의 평균 시도 횟수
+pub struct AttackRow {
+    pub bits: u32,
+    pub birthday: f64,
+    pub rho: f64,
+    pub preimage: Option<f64>, // n이 크면 너무 오래 걸려서 생략
+}
+
+pub fn run_experiments(collision_bits: &[u32], preimage_max_bits: u32, trials: usize, rng: &mut impl Rng) -> Vec<AttackRow> {
+    collision_bits
+        .iter()
+        .map(|&bits| {
+            let (mut birthday, mut rho, mut preimage) = (0u64, 0u64, 0u64);
+            for _ in 0..trials {
+                let h = TruncatedHash::new(bits, rng);
+
+                let (a, b, attempts) = birthday_collision(&h);
+                assert!(a != b && h.hash(a) == h.hash(b));
+                birthday += attempts;
+
+                // 로 공격이 포기하면 (해시가 순열에 가까움) 생일 공격 결과로 대신
+                let (a, b, attempts) = rho_collision(&h, rng).unwrap_or_else(|| birthday_collision(&h));
+                assert!(a != b && h.hash(a) == h.hash(b));
+                rho += attempts;
+
+                if bits <= preimage_max_bits {
+                    let target = h.hash(rng.gen_range(u64::MAX / 2..u64::MAX));
+                    let (found, attempts) = brute_force_preimage(&h, target);
+                    assert_eq!(h.hash(found), target);
+                    preimage += attempts;
+                }
+            }
+            let average = |total: u64| total as f64 / trials as f64;
+            AttackRow {
+                bits,
+                birthday: average(birthday),
+                rho: average(rho),
+                preimage: (bits <= preimage_max_bits).then(|| average(preimage)),
+            }
+        })
+        .collect()
+}
+
+pub fn print_table(rows: &[AttackRow]) {
+    println!("  n비트 | 생일 공격(log2) | 로 + 플로이드(log2) | 역상 전수조사(log2) | 이론: n/2, n");
+    for row in rows {
+        let preimage = row
+            .preimage
+            .map(|p| format!("{:>9.0} ({:>4.1})", p, p.log2()))
+            .unwrap_or_else(|| format!("{:>16}", "생략"));
+        println!(
+            "  {:>5} | {:>8.0} ({:>4.1}) | {:>11.0} ({:>4.1}) | {} | {:>4.1}, {:>4.1}",
+            row.bits,
+            row.birthday,
+            row.birthday.log2(),
+            row.rho,
+            row.rho.log2(),
+            preimage,
+            row.bits as f64 / 2.0,
+            row.bits
+        );
+    }
+}
+
+// 가로축 = log2(시도 횟수), 글자 위치로 그린 그래프: B 생일, R 로, P 역상
+pub fn print_plot(rows: &[AttackRow]) {
+    const SCALE: f64 = 2.0; // log2 한 칸 = 글자 2칸
+    let max_log = rows
+        .iter()
+        .flat_map(|row| [row.birthday, row.rho, row.preimage.unwrap_or(1.0)])
+        .fold(1.0, f64::max)
+        .log2()
+        .ceil();
+    let width = (max_log * SCALE) as usize + 2;
+
+    println!("\n  log2(시도 횟수) ->");
+    for row in rows {
+        let mut line = vec![' '; width];
+        let mut mark = |value: f64, symbol: char| {
+            let position = ((value.log2() * SCALE).round() as usize).min(width - 1);
+            line[position] = symbol;
+        };
+        mark(row.birthday, 'B');
+        mark(row.rho, 'R');
+        if let Some(preimage) = row.preimage {
+            mark(preimage, 'P');
+        }
+        println!("  n={:>2} |{}", row.bits, line.iter().collect::<String>());
+    }
+    let axis: String = (0..=max_log as usize).step_by(4).map(|tick| format!("{:<8}", tick)).collect();
+    println!("        {}", axis);
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    #[test]
+    fn outputs_fit_in_n_bits() {
+        let mut rng = StdRng::seed_from_u64(37);
+        for bits in [MIN_BITS, 8, 63, 64] {
+            let h = TruncatedHash::new(bits, &mut rng);
+            assert_eq!(h.max_output().count_ones(), bits);
+            assert!((0..100).all(|input| h.hash(input) <= h.max_output()));
+        }
+    }
+
+    #[test]
+    fn attacks_find_real_collisions_and_preimages() {
+        let mut rng = StdRng::seed_from_u64(37);
+        let h = TruncatedHash::new(12, &mut rng);
+        let (a, b, _) = birthday_collision(&h);
+        assert!(a != b && h.hash(a) == h.hash(b));
+        let (a, b, _) = rho_collision(&h, &mut rng).unwrap();
+        assert!(a != b && h.hash(a) == h.hash(b));
+        let (found, _) = brute_force_preimage(&h, h.hash(u64::MAX));
+        assert_eq!(h.hash(found), h.hash(u64::MAX));
+    }
+
+    #[test]
+    #[should_panic]
+    fn more_than_64_bits_is_rejected() {
+        TruncatedHash::new(65, &mut StdRng::seed_from_u64(37));
+    }
+
+    #[test]
+    fn rho_gives_up_on_a_permutation() {
+        // 1비트 해시는 new가 거부하니 직접 만듦: 0, 1 두 값뿐이라 x -> h(x)는 항상 꼬리 없는 고리
+        let mut rng = StdRng::seed_from_u64(37);
+        let permutations: Vec<TruncatedHash> = (0..8u8)
+            .map(|salt| TruncatedHash { bits: 1, salt: [salt; 8] })
+            .filter(|h| h.hash(0) != h.hash(1))
+            .collect();
+        assert!(!permutations.is_empty());
+        for h in &permutations {
+            assert_eq!(rho_collision(h, &mut rng), None);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_few_bits_are_rejected() {
+        TruncatedHash::new(MIN_BITS - 1, &mut StdRng::seed_from_u64(37));
+    }
+}
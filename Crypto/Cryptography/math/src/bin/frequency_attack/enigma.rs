@@ -0,0 +1,429 @@
+use crate::alphabet::{letters, map_letters};
+use crate::classical::{ClassicalCipher, KeyError};
+use crate::substitution::NgramModel;
+
+// [도구 11] 에니그마 I (독일군 3로터 암호기) + 튜링-웰치먼 봄브 방식 공격
+// 시저 암호 = 글자를 고정된 칸만큼 밀기
+// 에니그마   = 글자를 누를 때마다 로터가 돌아서, 매 글자가 "다른 치환표"로 암호화됨
+//   키보드 -> 플러그보드 -> 로터 3개 (오른쪽 -> 왼쪽) -> 반사판 -> 로터 3개 (왼쪽 -> 오른쪽) -> 플러그보드 -> 램프
+// 반사판 덕분에 같은 설정으로 한 번 더 누르면 복호화됨 (대신 어떤 글자도 자기 자신으로는 절대 안 바뀜 -> 약점)
+
+// 로터 I~V의 배선과 노치 (이 글자가 창에 보이다가 넘어갈 때 왼쪽 로터를 한 칸 밈)
+const ROTORS: [(&str, &str, u8); 5] = [
+    ("I", "EKMFLGDQVZNTOWYHXUSPAIBRCJ", b'Q'),
+    ("II", "AJDKSIRUXBLHWTMCQGZNPYFVOE", b'E'),
+    ("III", "BDFHJLCPRTXVZNYEIWGAKMUSQO", b'V'),
+    ("IV", "ESOVPZJAYQUIRHXLNFTGKDCMWB", b'J'),
+    ("V", "VZBRGITYUPSDNHLXAWMJQOFECK", b'Z'),
+];
+const REFLECTOR_B: &str = "YRUHQSLDPXNGOKMIEBFZCWVJAT";
+
+fn wiring(rotor: usize, inverse: bool) -> [u8; 26] {
+    let mut table = [0u8; 26];
+    for (i, b) in ROTORS[rotor].1.bytes().enumerate() {
+        if inverse {
+            table[(b - b'A') as usize] = i as u8;
+        } else {
+            table[i] = b - b'A';
+        }
+    }
+    table
+}
+
+// 플러그보드를 빼고 로터 + 반사판만 통과시키는 부분 (봄브가 흉내 내는 것도 이 부분)
+// cores = 로터별 (창 위치 - 링 설정): 배선이 실제로 얼마나 돌아가 있는지
+struct Scrambler {
+    forward: [[u8; 26]; 3],
+    backward: [[u8; 26]; 3],
+    reflector: [u8; 26],
+}
+
+impl Scrambler {
+    fn new(rotors: [usize; 3]) -> Self {
+        let reflector: Vec<u8> = letters(REFLECTOR_B);
+        Scrambler {
+            forward: rotors.map(|r| wiring(r, false)),
+            backward: rotors.map(|r| wiring(r, true)),
+            reflector: reflector.try_into().expect("반사판은 26글자"),
+        }
+    }
+
+    fn scramble(&self, cores: [u8; 3], letter: u8) -> u8 {
+        let through = |table: &[u8; 26], core: u8, x: u8| (table[((x + core) % 26) as usize] + 26 - core) % 26;
+        let mut x = letter;
+        for (table, &core) in self.forward.iter().zip(&cores).rev() {
+            x = through(table, core, x);
+        }
+        x = self.reflector[x as usize];
+        for (table, &core) in self.backward.iter().zip(&cores) {
+            x = through(table, core, x);
+        }
+        x
+    }
+}
+
+// 하루치 키 설정: 로터 순서(왼쪽, 가운데, 오른쪽), 링 설정, 시작 위치, 플러그보드
+pub struct Enigma {
+    rotors: [usize; 3],
+    rings: [u8; 3],
+    positions: [u8; 3],
+    plugboard: [u8; 26],
+}
+
+impl Enigma {
+    // 로터 번호는 0~4 (= I~V, 한 로터는 한 자리에만), 링/위치는 "AAA" 같은 3글자, 플러그는 "AB CD EF" 처럼 짝 목록
+    pub fn new(rotors: [usize; 3], rings: &str, positions: &str, plugs: &str) -> Result<Self, KeyError> {
+        for (slot, &rotor) in rotors.iter().enumerate() {
+            if rotor >= ROTORS.len() || rotors[..slot].contains(&rotor) {
+                return Err(KeyError::BadRotor(rotor));
+            }
+        }
+        let to_array = |text: &str| -> Result<[u8; 3], KeyError> {
+            letters(text).try_into().map_err(|_| KeyError::BadSetting(text.to_string()))
+        };
+        let mut plugboard: [u8; 26] = std::array::from_fn(|i| i as u8);
+        for plug in plugs.split_whitespace() {
+            // 서로 다른 두 글자여야 하고, 한 글자에 플러그 두 개는 못 꽂음
+            let pair: Vec<u8> = letters(plug);
+            let free = |x: u8| plugboard[x as usize] == x;
+            if pair.len() != 2 || pair[0] == pair[1] || !free(pair[0]) || !free(pair[1]) {
+                return Err(KeyError::BadPlug(plug.to_string()));
+            }
+            plugboard[pair[0] as usize] = pair[1];
+            plugboard[pair[1] as usize] = pair[0];
+        }
+        Ok(Enigma { rotors, rings: to_array(rings)?, positions: to_array(positions)?, plugboard })
+    }
+
+    // 오른쪽 로터는 매번, 노치를 넘으면 왼쪽 이웃도 같이 밈
+    // 가운데 로터는 자기 노치에서 한 번 더 밀리는 "이중 스텝" 버그가 있음
+    fn step(&self, positions: &mut [u8; 3]) {
+        let at_notch = |slot: usize, positions: &[u8; 3]| positions[slot] == ROTORS[self.rotors[slot]].2 - b'A';
+        if at_notch(1, positions) {
+            positions[0] = (positions[0] + 1) % 26;
+            positions[1] = (positions[1] + 1) % 26;
+        } else if at_notch(2, positions) {
+            positions[1] = (positions[1] + 1) % 26;
+        }
+        positions[2] = (positions[2] + 1) % 26;
+    }
+
+    // 알파벳만 암호화하고 대소문자는 제자리, 나머지 글자는 그대로 (로터도 알파벳에서만 돎)
+    // 암호화와 복호화가 같은 동작
+    pub fn process(&self, text: &str) -> String {
+        let scrambler = Scrambler::new(self.rotors);
+        let mut positions = self.positions;
+        map_letters(text, |letters| {
+            letters
+                .into_iter()
+                .map(|letter| {
+                    self.step(&mut positions); // 키를 누르면 로터가 먼저 돌고 나서 전류가 흐름
+                    let cores = std::array::from_fn(|slot| (positions[slot] + 26 - self.rings[slot]) % 26);
+                    let x = self.plugboard[letter as usize];
+                    let x = scrambler.scramble(cores, x);
+                    self.plugboard[x as usize] as i32
+                })
+                .collect()
+        })
+    }
+}
+
+impl ClassicalCipher for Enigma {
+    fn name(&self) -> String {
+        let text = |values: [u8; 3]| values.iter().map(|&v| (v + b'A') as char).collect::<String>();
+        let order: Vec<&str> = self.rotors.iter().map(|&r| ROTORS[r].0).collect();
+        format!("Enigma({} {}/{})", order.join("-"), text(self.rings), text(self.positions))
+    }
+
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.process(plaintext)
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> String {
+        self.process(ciphertext)
+    }
+}
+
+// [공격 0] 크립 위치 찾기: 에니그마는 글자를 자기 자신으로 못 바꾸니까
+// 크립(확실히 들어있을 평문 조각)을 한 칸씩 밀어보다가 같은 자리에 같은 글자가 있으면 그 위치는 탈락
+pub fn possible_crib_positions(ciphertext: &str, crib: &str) -> Vec<usize> {
+    let (ciphertext, crib): (Vec<u8>, Vec<u8>) = (letters(ciphertext), letters(crib));
+    if crib.is_empty() || crib.len() > ciphertext.len() {
+        return Vec::new();
+    }
+    (0..=ciphertext.len() - crib.len())
+        .filter(|&offset| crib.iter().zip(&ciphertext[offset..]).all(|(p, c)| p != c))
+        .collect()
+}
+
+// 봄브가 멈춘 자리 (= 해볼 만한 후보 설정)
+pub struct BombeStop {
+    pub rotors: [usize; 3],
+    pub cores: [u8; 3],             // 창 위치 - 링 설정 (봄브는 링을 모르므로 이 값만 찾음)
+    pub plugboard: [Option<u8>; 26], // 메뉴에서 논리적으로 따라 나온 플러그 짝
+}
+
+// [공격 1] 튜링-웰치먼 봄브
+// 크립의 i번째 글자 p와 암호문 c 사이에는 c = P(S_i(P(p))) 관계가 있음 (P: 플러그보드, S_i: i번째 스크램블러)
+// 이 쌍들을 그래프로 엮은 것이 "메뉴". 가장 많이 연결된 글자 L을 골라서
+// "L의 플러그 짝이 x라면?"을 26가지로 가정하고, 메뉴를 따라 나머지 글자의 짝을 연쇄적으로 유도함
+// 한 글자에 짝이 두 개 생기면 모순 -> 그 가정은 틀림. 26가지가 다 틀리면 이 로터 설정 자체가 틀림
+// (실제 봄브는 이걸 전기 회로로 동시에 했음. 플러그가 양방향이라는 것 = "대각선 보드")
+// 가정: 크립 구간 안에서는 가운데 로터가 안 돈다 (오른쪽 로터만 돈다) -> 봄브도 같은 가정을 씀
+// 크립이 비었거나 암호문 끝을 넘어가면 메뉴를 만들 수 없으니 멈춘 곳도 없음
+pub fn bombe(ciphertext: &str, crib: &str, crib_offset: usize, orders: &[[usize; 3]]) -> Vec<BombeStop> {
+    let ciphertext = letters(ciphertext);
+    let crib = letters(crib);
+    if crib.is_empty() || crib_offset.checked_add(crib.len()).is_none_or(|end| end > ciphertext.len()) {
+        return Vec::new();
+    }
+    let mut menu: Vec<Vec<(u8, usize)>> = vec![Vec::new(); 26]; // 글자 -> (연결된 글자, 크립 안 위치)
+    for (i, &p) in crib.iter().enumerate() {
+        let c = ciphertext[crib_offset + i];
+        menu[p as usize].push((c, crib_offset + i));
+        menu[c as usize].push((p, crib_offset + i));
+    }
+    let center = (0..26u8).max_by_key(|&letter| menu[letter as usize].len()).expect("알파벳은 26글자");
+
+    let mut stops = Vec::new();
+    let mut pending = Vec::new();
+    for &rotors in orders {
+        let scrambler = Scrambler::new(rotors);
+        for left_middle in 0..26 * 26 {
+            let (left, middle) = ((left_middle / 26) as u8, (left_middle % 26) as u8);
+            // 왼쪽/가운데가 고정이면 스크램블러는 오른쪽 로터 위치 26가지뿐 -> 미리 표로 만들어 둠
+            let tables: Vec<[u8; 26]> = (0..26)
+                .map(|right| std::array::from_fn(|x| scrambler.scramble([left, middle, right], x as u8)))
+                .collect();
+            for right in 0..26 {
+                // i번째 글자를 누를 때 오른쪽 로터는 i+1칸 돌아가 있음
+                let scramble = |i: usize, x: u8| tables[(right as usize + i + 1) % 26][x as usize];
+                for guess in 0..26 {
+                    if let Some(plugboard) = follow_menu(&menu, center, guess, &scramble, &mut pending) {
+                        stops.push(BombeStop { rotors, cores: [left, middle, right], plugboard });
+                    }
+                }
+            }
+        }
+    }
+    stops
+}
+
+// "center의 짝은 guess"라는 가정에서 출발해 메뉴를 따라가며 플러그 짝을 유도. 모순이면 None
+fn follow_menu(
+    menu: &[Vec<(u8, usize)>],
+    center: u8,
+    guess: u8,
+    scramble: &impl Fn(usize, u8) -> u8,
+    pending: &mut Vec<(u8, u8)>,
+) -> Option<[Option<u8>; 26]> {
+    let mut partner: [Option<u8>; 26] = [None; 26];
+    pending.clear();
+    pending.push((center, guess));
+    while let Some((a, b)) = pending.pop() {
+        match (partner[a as usize], partner[b as usize]) {
+            (Some(x), _) if x == b => continue,
+            (Some(_), _) => return None,
+            (None, Some(_)) => return None,
+            (None, None) => {}
+        }
+        partner[a as usize] = Some(b);
+        partner[b as usize] = Some(a);
+        // 메뉴에서 a에 연결된 글자 o: P(o) = S_i(P(a)) = S_i(b), 대각선 보드 때문에 b 쪽도 똑같이
+        for &(other, i) in &menu[a as usize] {
+            pending.push((other, scramble(i, b)));
+        }
+        for &(other, i) in &menu[b as usize] {
+            pending.push((other, scramble(i, a)));
+        }
+    }
+    Some(partner)
+}
+
+pub struct RecoveredKey {
+    pub enigma: Enigma,
+    pub plaintext: String,
+    pub score: f64,
+}
+
+// [공격 2] 봄브가 멈춘 자리마다 남은 키를 채워서 전체 암호문을 해독해보고 가장 영어다운 것을 고름
+// 1. 링 설정: 봄브가 찾은 코어 위치는 그대로 두고(창 위치 = 코어 + 링) 이웃 로터가 넘어가는 시점만 바뀜
+//    오른쪽 링 26가지 -> 가운데 링 26가지 순서로 하나씩 고정 (가운데 링은 왼쪽 로터가 넘어갈 때만 티가 남)
+// 2. 메뉴에 안 나온 플러그: 아무 두 글자를 이어봐서 점수가 오르면 유지 (언덕 오르기)
+pub fn recover_key(ciphertext: &str, stops: &[BombeStop], model: &NgramModel) -> Option<RecoveredKey> {
+    let mut best: Option<RecoveredKey> = None;
+    for stop in stops {
+        let mut plugboard: [u8; 26] = std::array::from_fn(|i| stop.plugboard[i].unwrap_or(i as u8));
+        let known: Vec<bool> = stop.plugboard.iter().map(Option::is_some).collect();
+
+        let try_key = |rings: [u8; 3], plugboard: &[u8; 26]| {
+            let enigma = Enigma {
+                rotors: stop.rotors,
+                rings,
+                positions: std::array::from_fn(|slot| (stop.cores[slot] + rings[slot]) % 26),
+                plugboard: *plugboard,
+            };
+            let plaintext = enigma.process(ciphertext);
+            let score = model.score(&letters(&plaintext));
+            RecoveredKey { enigma, plaintext, score }
+        };
+
+        let mut rings = [0u8; 3];
+        for slot in [2, 1] {
+            rings[slot] = (0..26)
+                .map(|ring| {
+                    let mut trial = rings;
+                    trial[slot] = ring;
+                    (ring, try_key(trial, &plugboard).score)
+                })
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .expect("링 설정은 26가지")
+                .0;
+        }
+        let mut candidate = try_key(rings, &plugboard);
+
+        let mut improved = true;
+        while improved {
+            improved = false;
+            let free = |plugboard: &[u8; 26], x: usize| !known[x] && plugboard[x] == x as u8;
+            for a in 0..26 {
+                for b in a + 1..26 {
+                    if !free(&plugboard, a) || !free(&plugboard, b) {
+                        continue;
+                    }
+                    plugboard.swap(a, b); // 둘 다 비어 있으면 swap = a와 b를 플러그로 연결
+                    let trial = try_key(rings, &plugboard);
+                    if trial.score > candidate.score {
+                        candidate = trial;
+                        improved = true;
+                    } else {
+                        plugboard.swap(a, b);
+                    }
+                }
+            }
+        }
+
+        if best.as_ref().is_none_or(|best| candidate.score > best.score) {
+            best = Some(candidate);
+        }
+    }
+    best
+}
+
+impl RecoveredKey {
+    pub fn rotors(&self) -> [usize; 3] {
+        self.enigma.rotors
+    }
+
+    pub fn cores(&self) -> [u8; 3] {
+        std::array::from_fn(|slot| (self.enigma.positions[slot] + 26 - self.enigma.rings[slot]) % 26)
+    }
+
+    pub fn rings(&self) -> [u8; 3] {
+        self.enigma.rings
+    }
+
+    pub fn plugs(&self) -> String {
+        let board = &self.enigma.plugboard;
+        (0..26u8)
+            .filter(|&a| board[a as usize] > a)
+            .map(|a| format!("{}{}", (a + b'A') as char, (board[a as usize] + b'A') as char))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+// 로터 available개 중 3개를 골라 끼우는 순서 (로터 3개면 6가지, 5개면 60가지)
+pub fn rotor_orders(available: usize) -> Vec<[usize; 3]> {
+    let mut orders = Vec::new();
+    for left in 0..available {
+        for middle in (0..available).filter(|&m| m != left) {
+            for right in (0..available).filter(|&r| r != left && r != middle) {
+                orders.push([left, middle, right]);
+            }
+        }
+    }
+    orders
+}
+
+pub fn rotor_names(rotors: [usize; 3]) -> String {
+    rotors.iter().map(|&r| ROTORS[r].0).collect::<Vec<_>>().join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn textbook_vector_and_double_step() {
+        assert_eq!(Enigma::new([0, 1, 2], "AAA", "AAA", "").unwrap().encrypt("AAAAA"), "BDZGO");
+        // 가운데 로터가 노치(E)에 오면 다음 글자에서 왼쪽과 함께 한 번 더 돎: ADU -> ADV -> AEW -> BFX
+        let enigma = Enigma::new([0, 1, 2], "AAA", "ADU", "").unwrap();
+        let mut positions = enigma.positions;
+        for expected in [*b"ADV", *b"AEW", *b"BFX"] {
+            enigma.step(&mut positions);
+            assert_eq!(positions.map(|p| p + b'A'), expected);
+        }
+    }
+
+    #[test]
+    fn round_trip_keeps_case_and_non_letters() {
+        let enigma = Enigma::new([1, 2, 0], "BUL", "DKR", "AV BS CG DL FU HZ IN KM OW RX").unwrap();
+        let mut rng = StdRng::seed_from_u64(40);
+        for _ in 0..50 {
+            let text: String = (0..rng.gen_range(0..40)).map(|_| rng.r#gen::<char>()).chain("Attack at dawn, 5시!".chars()).collect();
+            let encrypted = enigma.encrypt(&text);
+            assert_eq!(enigma.decrypt(&encrypted), text);
+            for (a, b) in text.chars().zip(encrypted.chars()) {
+                assert_eq!(a.is_ascii_alphabetic(), b.is_ascii_alphabetic());
+                assert_eq!(a.is_ascii_lowercase(), b.is_ascii_lowercase());
+                if !a.is_ascii_alphabetic() {
+                    assert_eq!(a, b);
+                }
+            }
+        }
+        // 빈칸과 소문자는 로터를 돌리지 않음
+        assert_eq!(enigma.encrypt("attack at dawn").to_ascii_uppercase().replace(' ', ""), enigma.encrypt("ATTACKATDAWN"));
+    }
+
+    #[test]
+    fn bad_keys_are_rejected() {
+        assert_eq!(Enigma::new([0, 1, 5], "AAA", "AAA", "").err(), Some(KeyError::BadRotor(5)));
+        assert_eq!(Enigma::new([2, 1, 2], "AAA", "AAA", "").err(), Some(KeyError::BadRotor(2)));
+        assert_eq!(Enigma::new([0, 1, 2], "AA", "AAA", "").err(), Some(KeyError::BadSetting("AA".to_string())));
+        assert_eq!(Enigma::new([0, 1, 2], "AAA", "A1B2C3D", "").err(), Some(KeyError::BadSetting("A1B2C3D".to_string())));
+        assert_eq!(Enigma::new([0, 1, 2], "AAA", "AAA", "AB C").err(), Some(KeyError::BadPlug("C".to_string())));
+        assert_eq!(Enigma::new([0, 1, 2], "AAA", "AAA", "AA").err(), Some(KeyError::BadPlug("AA".to_string())));
+        assert_eq!(Enigma::new([0, 1, 2], "AAA", "AAA", "AB BC").err(), Some(KeyError::BadPlug("BC".to_string())));
+        assert!(Enigma::new([4, 3, 2], "a-b-c", "zzz", "ab cd").is_ok());
+    }
+
+    #[test]
+    fn no_letter_encrypts_to_itself() {
+        let enigma = Enigma::new([0, 1, 2], "AAA", "AAA", "AB CD").unwrap();
+        let text = "A".repeat(26 * 26);
+        assert!(enigma.encrypt(&text).bytes().all(|b| b != b'A'));
+    }
+
+    #[test]
+    fn crib_past_the_end_is_rejected() {
+        let ciphertext = Enigma::new([0, 1, 2], "AAA", "AAA", "").unwrap().encrypt("WEATHER");
+        assert!(possible_crib_positions(&ciphertext, "WEATHERFORECAST").is_empty());
+        assert!(possible_crib_positions(&ciphertext, "").is_empty());
+        assert!(bombe(&ciphertext, "WEATHER", 1, &[[0, 1, 2]]).is_empty());
+        assert!(bombe(&ciphertext, "WEATHER", usize::MAX, &[[0, 1, 2]]).is_empty());
+        assert!(bombe(&ciphertext, "", 0, &[[0, 1, 2]]).is_empty());
+    }
+
+    #[test]
+    fn bombe_stops_at_the_true_setting() {
+        let enigma = Enigma::new([0, 1, 2], "AAA", "AAW", "AV BS CG DL").unwrap(); // 크립 구간에서 가운데 로터가 안 돌도록
+        let plaintext = "WEATHERFORECASTNORTHSEA";
+        let ciphertext = enigma.encrypt(plaintext);
+        let stops = bombe(&ciphertext, plaintext, 0, &[[0, 1, 2]]);
+        assert!(stops.iter().any(|stop| stop.cores == [0, 0, 22] && stop.plugboard[0] == Some(21)));
+    }
+}
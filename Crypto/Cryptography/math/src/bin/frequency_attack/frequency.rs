@@ -0,0 +1,118 @@
+use crate::hangul;
+
+// [언어별 글자 빈도표] 빈도 분석 공격의 "정답지"
+// 암호문의 글자 분포를 이 표와 비교해서 얼마나 자연스러운 문장인지 점수를 매김
+pub struct Language {
+    pub name: &'static str,
+    pub letters: &'static [char],
+    pub frequencies: &'static [f64], // 퍼센트(%)
+}
+
+// 영어 알파벳 빈도 (E T A O I N ... 순으로 많이 나옴)
+pub const ENGLISH: Language = Language {
+    name: "영어",
+    letters: &[
+        'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M',
+        'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+    ],
+    frequencies: &[
+        8.167, 1.492, 2.782, 4.253, 12.702, 2.228, 2.015, 6.094, 6.966, 0.153, 0.772, 4.025, 2.406,
+        6.749, 7.507, 1.929, 0.095, 5.987, 6.327, 9.056, 2.758, 0.978, 2.360, 0.150, 1.974, 0.074,
+    ],
+};
+
+// 한글 기본 자모 24자 빈도 (겹자모는 ㄲ -> ㄱㄱ, ㅘ -> ㅗㅏ 처럼 풀어서 센 대략적인 값)
+// 'ㅇ'(받침 없는 초성)과 'ㅏ', 'ㅣ'(ㅐ ㅔ ㅚ ㅢ 안에도 들어감)가 압도적으로 많음
+pub const KOREAN_JAMO: Language = Language {
+    name: "한글 자모",
+    letters: &[
+        'ㄱ', 'ㄴ', 'ㄷ', 'ㄹ', 'ㅁ', 'ㅂ', 'ㅅ', 'ㅇ', 'ㅈ', 'ㅊ', 'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ',
+        'ㅏ', 'ㅑ', 'ㅓ', 'ㅕ', 'ㅗ', 'ㅛ', 'ㅜ', 'ㅠ', 'ㅡ', 'ㅣ',
+    ],
+    frequencies: &[
+        7.0, 7.5, 4.2, 5.4, 3.3, 2.2, 4.6, 10.8, 3.6, 1.2, 0.4, 0.9, 0.6, 3.3,
+        12.0, 0.4, 5.3, 2.5, 4.6, 0.7, 3.3, 0.4, 5.9, 9.9,
+    ],
+};
+
+impl Language {
+    // 알파벳 안에서의 위치 (영어는 대소문자 구분 없이)
+    pub fn index_of(&self, c: char) -> Option<usize> {
+        let c = c.to_ascii_uppercase();
+        self.letters.iter().position(|&letter| letter == c)
+    }
+
+    // 분석 전에 글자만 남기기 (한글 음절은 기본 자모로 풀어서)
+    pub fn normalize(&self, text: &str) -> Vec<usize> {
+        hangul::decompose(text).chars().filter_map(|c| self.index_of(c)).collect()
+    }
+}
+
+pub fn letter_counts(letters: &[usize], language: &Language) -> Vec<usize> {
+    let mut counts = vec![0; language.letters.len()];
+    for &index in letters {
+        counts[index] += 1;
+    }
+    counts
+}
+
+// 카이제곱 통계량: sum (관측 - 기대)^2 / 기대  -> 0에 가까울수록 그 언어다운 문장
+pub fn chi_squared(letters: &[usize], language: &Language) -> f64 {
+    if letters.is_empty() {
+        return 0.0; // 비교할 글자가 없음 (0/0 = NaN 방지)
+    }
+    let total = letters.len() as f64;
+    letter_counts(letters, language)
+        .iter()
+        .zip(language.frequencies)
+        .map(|(&observed, &percent)| {
+            let expected = total * percent / 100.0;
+            (observed as f64 - expected).powi(2) / expected
+        })
+        .sum()
+}
+
+// 일치 지수(IoC): 글자 두 개를 아무렇게나 뽑았을 때 같은 글자일 확률
+// 영어 문장 ≈ 0.066, 완전 랜덤 ≈ 1/26 ≈ 0.038 (단일 치환은 영어 값 그대로, 다중 치환은 랜덤 쪽으로 내려감)
+pub fn index_of_coincidence(letters: &[usize], alphabet_size: usize) -> f64 {
+    let total = letters.len() as f64;
+    if total < 2.0 {
+        return 0.0;
+    }
+    let mut counts = vec![0usize; alphabet_size];
+    for &index in letters {
+        counts[index] += 1;
+    }
+    let pairs: usize = counts.iter().map(|&count| count * count.saturating_sub(1)).sum();
+    pairs as f64 / (total * (total - 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chi_squared_of_nothing_is_zero() {
+        assert_eq!(chi_squared(&[], &ENGLISH), 0.0);
+        assert_eq!(chi_squared(&[], &KOREAN_JAMO), 0.0);
+    }
+
+    #[test]
+    fn english_scores_better_than_gibberish() {
+        let english = ENGLISH.normalize("the quick brown fox jumps over the lazy dog and then sleeps in the sun");
+        let gibberish = ENGLISH.normalize("zqxj vkwz qqzx jjvk zxqw kvjz qzxv wqjk zzqx vjwk qxzj");
+        assert!(chi_squared(&english, &ENGLISH) < chi_squared(&gibberish, &ENGLISH));
+    }
+
+    #[test]
+    fn normalize_splits_syllables_into_jamo() {
+        assert_eq!(KOREAN_JAMO.normalize("까 a").len(), 3); // ㄱ ㄱ ㅏ
+        assert_eq!(ENGLISH.normalize("Ab, c!"), [0, 1, 2]);
+    }
+
+    #[test]
+    fn index_of_coincidence_of_repeats() {
+        assert_eq!(index_of_coincidence(&[0, 0, 0], 26), 1.0);
+        assert_eq!(index_of_coincidence(&[0], 26), 0.0);
+    }
+}
@@ -0,0 +1,117 @@
+// [한글 분해/조합] 완성형 음절(가~힣)을 기본 자모 24자로 풀고, 다시 음절로 묶기
+// 음절 코드 = 0xAC00 + (초성 * 21 + 중성) * 28 + 종성
+const SYLLABLE_BASE: u32 = 0xAC00;
+const SYLLABLE_LAST: u32 = 0xD7A3;
+
+const CHOSEONG: [&str; 19] = [
+    "ㄱ", "ㄱㄱ", "ㄴ", "ㄷ", "ㄷㄷ", "ㄹ", "ㅁ", "ㅂ", "ㅂㅂ", "ㅅ",
+    "ㅅㅅ", "ㅇ", "ㅈ", "ㅈㅈ", "ㅊ", "ㅋ", "ㅌ", "ㅍ", "ㅎ",
+];
+
+const JUNGSEONG: [&str; 21] = [
+    "ㅏ", "ㅏㅣ", "ㅑ", "ㅑㅣ", "ㅓ", "ㅓㅣ", "ㅕ", "ㅕㅣ", "ㅗ", "ㅗㅏ", "ㅗㅏㅣ",
+    "ㅗㅣ", "ㅛ", "ㅜ", "ㅜㅓ", "ㅜㅓㅣ", "ㅜㅣ", "ㅠ", "ㅡ", "ㅡㅣ", "ㅣ",
+];
+
+const JONGSEONG: [&str; 28] = [
+    "", "ㄱ", "ㄱㄱ", "ㄱㅅ", "ㄴ", "ㄴㅈ", "ㄴㅎ", "ㄷ", "ㄹ", "ㄹㄱ", "ㄹㅁ", "ㄹㅂ", "ㄹㅅ", "ㄹㅌ",
+    "ㄹㅍ", "ㄹㅎ", "ㅁ", "ㅂ", "ㅂㅅ", "ㅅ", "ㅅㅅ", "ㅇ", "ㅈ", "ㅊ", "ㅋ", "ㅌ", "ㅍ", "ㅎ",
+];
+
+// "한글" -> "ㅎㅏㄴㄱㅡㄹ" (음절이 아닌 글자는 그대로)
+pub fn decompose(text: &str) -> String {
+    let mut jamo = String::new();
+    for c in text.chars() {
+        let code = c as u32;
+        if (SYLLABLE_BASE..=SYLLABLE_LAST).contains(&code) {
+            let offset = code - SYLLABLE_BASE;
+            jamo.push_str(CHOSEONG[(offset / (21 * 28)) as usize]);
+            jamo.push_str(JUNGSEONG[((offset / 28) % 21) as usize]);
+            jamo.push_str(JONGSEONG[(offset % 28) as usize]);
+        } else {
+            jamo.push(c);
+        }
+    }
+    jamo
+}
+
+// "ㅎㅏㄴㄱㅡㄹ" -> "한글" (decompose의 반대, 음절로 못 묶는 자모는 그대로)
+// 모음 사이 자음은 마지막 하나만 다음 음절의 초성, 나머지는 앞 음절의 받침으로 나눔
+// 겹자음을 두 글자로 풀었기 때문에 모음 사이 "ㄱㄱ"은 '학교'처럼 받침 + 초성이 되고
+// '아까'는 '악가'로 돌아옴 (decompose(compose(x)) == x는 항상 성립)
+pub fn compose(jamo: &str) -> String {
+    let chars: Vec<char> = jamo.chars().collect();
+    let mut text = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match syllable_at(&chars, i) {
+            Some((syllable, length)) => {
+                text.push(syllable);
+                i += length;
+            }
+            None => {
+                text.push(chars[i]);
+                i += 1;
+            }
+        }
+    }
+    text
+}
+
+// i에서 시작하는 음절 하나 (음절, 쓴 자모 수)
+fn syllable_at(chars: &[char], i: usize) -> Option<(char, usize)> {
+    let is_vowel = |at: usize| chars.get(at).is_some_and(|&c| ('ㅏ'..='ㅣ').contains(&c));
+    let is_consonant = |at: usize| chars.get(at).is_some_and(|&c| ('ㄱ'..='ㅎ').contains(&c));
+
+    // 초성: 바로 뒤에 모음이 와야 함 (쌍자음 먼저)
+    let (choseong, mut end) = [2, 1].iter().find_map(|&length| {
+        let index = position(&CHOSEONG, chars.get(i..i + length)?)?;
+        is_vowel(i + length).then_some((index, i + length))
+    })?;
+
+    // 중성: 가장 긴 겹모음부터
+    let (jungseong, length) = [3, 2, 1]
+        .iter()
+        .find_map(|&length| Some((position(&JUNGSEONG, chars.get(end..end + length)?)?, length)))?;
+    end += length;
+
+    // 종성: 이어지는 자음 중 다음 음절의 초성으로 갈 마지막 하나를 뺀 나머지
+    let run = (end..).take_while(|&at| is_consonant(at)).count();
+    let available = if is_vowel(end + run) { run.saturating_sub(1) } else { run };
+    let (jongseong, length) = (0..=available.min(2))
+        .rev()
+        .find_map(|length| Some((position(&JONGSEONG, &chars[end..end + length])?, length)))?;
+    end += length;
+
+    let code = SYLLABLE_BASE + ((choseong * 21 + jungseong) * 28 + jongseong) as u32;
+    Some((char::from_u32(code)?, end - i))
+}
+
+fn position(table: &[&str], jamo: &[char]) -> Option<usize> {
+    table.iter().position(|entry| entry.chars().eq(jamo.iter().copied()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compose_reverses_decompose() {
+        for text in ["한글", "비트코인은 누구나 장부를 복사해서 들고 있기 때문에", "값이 없었다", "괜찮아 뭐해", "Hello 세계!"] {
+            assert_eq!(compose(&decompose(text)), text);
+        }
+    }
+
+    #[test]
+    fn ambiguous_double_consonant_goes_to_the_final() {
+        assert_eq!(compose(&decompose("아까")), "악가");
+        assert_eq!(compose(&decompose("학교")), "학교");
+    }
+
+    #[test]
+    fn loose_jamo_survive_the_round_trip() {
+        for jamo in ["ㅏㄱㄴ", "ㅋㅋㅋ", "ㄱㄴㄷㅏㅏㅣㅣ", "ㅎㅗㅏㅣㄹㄱㅅㄷ", ""] {
+            assert_eq!(decompose(&compose(jamo)), jamo);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sha2::digest::generic_array::GenericArray;
+
+use crate::get_hash;
+
+// [도구 9] 길이 확장 공격 (Length-Extension Attack)
+// "해시는 지문이니까 MAC = SHA256(비밀키 || 메시지) 로 쓰면 되겠지?" -> 틀림!
+// SHA-256 결과값 = 마지막 블록까지 처리한 내부 상태(8개 워드) 그 자체
+// -> 해커는 비밀키를 몰라도, 그 상태에서 이어서 블록을 더 넣어 "메시지 + 패딩 + 추가문" 의 올바른 태그를 계산할 수 있음
+const BLOCK_SIZE: usize = 64;
+const TAG_SIZE: usize = 32; // SHA-256 출력 = 내부 상태 8워드
+
+#[derive(Debug, PartialEq)]
+pub enum TagError {
+    NotHex,
+    WrongLength(usize),
+}
+
+impl fmt::Display for TagError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TagError::NotHex => write!(f, "태그가 hex 문자열이 아님"),
+            TagError::WrongLength(len) => write!(f, "태그가 {}바이트 (SHA-256 태그는 {}바이트)", len, TAG_SIZE),
+        }
+    }
+}
+
+// 취약한 서버: 태그 = SHA256(key || message)
+pub struct NaiveMacServer {
+    key: Vec<u8>,
+}
+
+impl NaiveMacServer {
+    pub fn new(key: &[u8]) -> Self {
+        NaiveMacServer { key: key.to_vec() }
+    }
+
+    pub fn tag(&self, message: &[u8]) -> String {
+        get_hash([self.key.as_slice(), message].concat())
+    }
+
+    pub fn verify(&self, message: &[u8], tag: &str) -> bool {
+        self.tag(message) == tag
+    }
+}
+
+// 고친 서버: HMAC-SHA256 = H((K ^ opad) || H((K ^ ipad) || message))
+// 바깥쪽 해시가 안쪽 결과를 한 번 더 감싸기 때문에 내부 상태가 밖으로 드러나지 않음
+pub struct HmacServer {
+    key: Vec<u8>,
+}
+
+impl HmacServer {
+    pub fn new(key: &[u8]) -> Self {
+        HmacServer { key: key.to_vec() }
+    }
+
+    pub fn tag(&self, message: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC은 어떤 키 길이든 받음");
+        mac.update(message);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    pub fn verify(&self, message: &[u8], tag: &str) -> bool {
+        let Ok(tag) = hex::decode(tag) else { return false };
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC은 어떤 키 길이든 받음");
+        mac.update(message);
+        mac.verify_slice(&tag).is_ok()
+    }
+}
+
+// SHA-256 패딩: 0x80, 0으로 채우기, 마지막 8바이트에 전체 비트 길이 (빅 엔디언)
+pub fn sha256_padding(message_len: usize) -> Vec<u8> {
+    let mut padding = vec![0x80];
+    while (message_len + padding.len()) % BLOCK_SIZE != BLOCK_SIZE - 8 {
+        padding.push(0);
+    }
+    padding.extend_from_slice(&((message_len as u64) * 8).to_be_bytes());
+    padding
+}
+
+// 원래 태그(= 내부 상태)에서 출발해서 suffix를 이어 붙인 태그를 계산
+// secret_len = 비밀키 + 원래 메시지 길이 (해커는 키 길이만 추측하면 됨)
+pub fn extend_tag(original_tag: &str, secret_len: usize, suffix: &[u8]) -> Result<String, TagError> {
+    let digest = hex::decode(original_tag).map_err(|_| TagError::NotHex)?;
+    if digest.len() != TAG_SIZE {
+        return Err(TagError::WrongLength(digest.len()));
+    }
+    let mut state: [u32; 8] = std::array::from_fn(|i| u32::from_be_bytes(digest[i * 4..i * 4 + 4].try_into().unwrap()));
+
+    // 서버가 해시할 전체 길이 = 비밀키 + 메시지 + 접착 패딩 + 추가문
+    let processed = secret_len + sha256_padding(secret_len).len();
+    let mut tail = suffix.to_vec();
+    tail.extend(sha256_padding(processed + suffix.len()));
+
+    let blocks: Vec<GenericArray<u8, _>> = tail.chunks(BLOCK_SIZE).map(GenericArray::clone_from_slice).collect();
+    sha2::compress256(&mut state, &blocks);
+
+    Ok(state.iter().map(|word| hex::encode(word.to_be_bytes())).collect())
+}
+
+// 키 길이를 모르니 1~64바이트를 전부 넣어보고 서버가 받아주는 것을 찾음
+// verify = 서버에 (메시지, 태그)를 보내보는 것 (예: API 호출이 성공하는지)
+// 엿본 태그가 SHA-256 태그 모양이 아니면 시작도 못 함 (Err), 어떤 키 길이로도 안 통하면 Ok(None)
+pub fn forge(
+    message: &[u8],
+    tag: &str,
+    suffix: &[u8],
+    verify: impl Fn(&[u8], &str) -> bool,
+) -> Result<Option<(usize, Vec<u8>, String)>, TagError> {
+    for key_len in 1..=64 {
+        let mut forged = message.to_vec();
+        forged.extend(sha256_padding(key_len + message.len()));
+        forged.extend_from_slice(suffix);
+        let forged_tag = extend_tag(tag, key_len + message.len(), suffix)?;
+        if verify(&forged, &forged_tag) {
+            return Ok(Some((key_len, forged, forged_tag)));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn padding_fills_the_last_block() {
+        for len in [0, 1, 55, 56, 63, 64, 100] {
+            let padding = sha256_padding(len);
+            assert_eq!((len + padding.len()) % BLOCK_SIZE, 0);
+            assert_eq!(padding[0], 0x80);
+            assert_eq!(padding[padding.len() - 8..], ((len as u64) * 8).to_be_bytes());
+        }
+    }
+
+    #[test]
+    fn extended_tag_matches_hashing_the_whole_message() {
+        let secret = b"key+message";
+        let mut whole = secret.to_vec();
+        whole.extend(sha256_padding(secret.len()));
+        whole.extend_from_slice(b"&admin=true");
+        assert_eq!(extend_tag(&get_hash(secret), secret.len(), b"&admin=true"), Ok(get_hash(&whole)));
+    }
+
+    #[test]
+    fn malformed_tags_are_rejected() {
+        assert_eq!(extend_tag("not hex", 10, b"&admin=true"), Err(TagError::NotHex));
+        assert_eq!(extend_tag("abcd", 10, b"&admin=true"), Err(TagError::WrongLength(2)));
+        assert_eq!(extend_tag(&"00".repeat(33), 10, b"&admin=true"), Err(TagError::WrongLength(33)));
+        assert_eq!(forge(b"amount=1", "abcd", b"&amount=9", |_, _| true), Err(TagError::WrongLength(2)));
+    }
+
+    #[test]
+    fn naive_mac_is_forged_and_hmac_is_not() {
+        let naive = NaiveMacServer::new(b"0123456789");
+        let (key_len, forged, forged_tag) =
+            forge(b"amount=1", &naive.tag(b"amount=1"), b"&amount=9", |m, t| naive.verify(m, t)).unwrap().unwrap();
+        assert_eq!(key_len, 10);
+        assert!(forged.starts_with(b"amount=1") && forged.ends_with(b"&amount=9"));
+        assert!(naive.verify(&forged, &forged_tag));
+
+        let hmac = HmacServer::new(b"0123456789");
+        assert!(hmac.verify(b"amount=1", &hmac.tag(b"amount=1")));
+        assert!(!hmac.verify(b"amount=1", "not hex"));
+        assert_eq!(forge(b"amount=1", &hmac.tag(b"amount=1"), b"&amount=9", |m, t| hmac.verify(m, t)), Ok(None));
+    }
+}
@@ -0,0 +1,435 @@
+mod alphabet;
+mod avalanche;
+mod caesar;
+mod classical;
+mod collision;
+mod enigma;
+mod frequency;
+mod hangul;
+mod length_extension;
+mod password_audit;
+mod report;
+mod substitution;
+mod vigenere;
+
+use std::time::{Duration, Instant};
+
+use sha2::{Sha256, Digest};
+use avalanche::{AvalancheReport, HASH_FUNCTIONS};
+use caesar::{CaesarGuess, break_caesar, rotate};
+use classical::{Affine, Caesar, ClassicalCipher, ColumnarTransposition, Hill, Playfair, Vigenere};
+use enigma::Enigma;
+use frequency::{ENGLISH, KOREAN_JAMO, Language};
+use length_extension::{HmacServer, NaiveMacServer};
+use password_audit::{RainbowTable, Scheme, StoredPassword};
+use substitution::{NgramModel, SubstitutionKey, break_substitution};
+
+fn main() {
+    // 보고서 모드: cargo run --bin frequency_attack -- report [파일]
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("report") {
+        if let Err(err) = report::run(args.get(2).map(String::as_str)) {
+            eprintln!("입력을 읽을 수 없음: {}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // 1. 테스트 데이터:패턴이 아주 뚜렷한 문장 (AAAA... 같은 느낌)
+    // 'E'가 가장 많이 나오고, 'L'이 두 번 연속 반복되는 패턴이 있음
+    let input = "HELLO HELLO HELLO";
+
+    println!("--- [실험 1] 고전 암호 (치환 암호) ---");
+    println!("원본 데이터: {}", input);
+
+    // 3칸씩 뒤로 무리는 '시저 암호' 적용
+    let weak_encrypted = caesar_cipher(input, 3);
+    println!("암호화 결과: {}", weak_encrypted);
+
+    println!("\n[분석] 해커가 가능한 이동 26가지를 전부 풀어보고 영어 빈도표와 비교");
+    let guesses = attack_caesar(&weak_encrypted, &ENGLISH);
+    let rank = guesses.iter().position(|guess| guess.shift == 3).unwrap() + 1;
+    println!("-> 정답(3칸)은 {}위. 글자가 H, E, L, O 4종류뿐이라 영어 빈도표와 잘 안 맞음", rank);
+
+    // 문장이 길수록 빈도표와 더 잘 맞음
+    let long_text = "Blockchain security does not come from hiding the algorithm. \
+        It comes from keys that are too large to guess and hashes that leak no pattern at all.";
+    let long_encrypted = caesar_cipher(long_text, 11);
+    println!("\n[긴 영어 문장] 암호문: {}", long_encrypted);
+    let best = &attack_caesar(&long_encrypted, &ENGLISH)[0];
+    assert_eq!(best.shift, 11);
+    println!("-> 해커 왈: 키는 {}칸! 문장이 길어지니 바로 1위 (빈도 분석 성공 🚨)", best.shift);
+
+    // 한글은 음절을 자모로 풀어서 24자 알파벳 안에서 밀어버림
+    let korean_text = "비트코인은 누구나 장부를 복사해서 들고 있기 때문에 한 사람이 마음대로 기록을 고칠 수 없다. \
+        대신 모든 거래가 공개되어 있어서 주소만 알면 잔고를 누구나 들여다볼 수 있다.";
+    let korean_encrypted = rotate(korean_text, 5, &KOREAN_JAMO);
+    println!("\n[한글 문장] 암호문: {}", korean_encrypted);
+    let best = &attack_caesar(&korean_encrypted, &KOREAN_JAMO)[0];
+    assert_eq!(best.shift, 5);
+    println!("-> 해커 왈: 'ㅇ', 'ㅏ', 'ㅣ'가 제일 많을 테니 키는 {}칸! (빈도 분석 성공 🚨)", best.shift);
+
+
+
+    println!("\n--- [실험 1-2] 단일 치환 암호 (알파벳을 통째로 섞기) ---");
+    // 키가 26! 가지라서 시저처럼 전부 풀어볼 수는 없음
+    let mut rng = rand::thread_rng();
+    let secret_text = "When you send coins to a friend, the network does not care who you are. \
+        It only checks that the signature matches the key that owns the coins. \
+        That is why losing the seed phrase means losing the money forever, \
+        and why anyone who copies it can quietly take everything without asking. \
+        Keep the seed offline, write it on paper, and never type it into a website that asks for it.";
+    let secret_key = SubstitutionKey::random(&mut rng);
+    let substituted = substitution::encrypt(secret_text, &secret_key);
+    println!("비밀 키  : {}", secret_key);
+    println!("암호문   : {}", substituted);
+
+    // 1. 시저 해독기를 들이밀면? -> 한 칸씩 미는 키가 아니라서 실패
+    let caesar_try = &break_caesar(&substituted, &ENGLISH)[0];
+    println!("\n[시저 해독기] {}", caesar_try.plaintext);
+
+    // 2. 글자 묶음(n-gram) 점수로 언덕 오르기
+    for n in [2, 4] {
+        let model = NgramModel::english(n);
+        let guess = break_substitution(&substituted, &model, 20, &mut rng);
+        let correct = secret_text.chars().zip(guess.plaintext.chars()).filter(|(a, b)| a == b).count();
+        println!("\n[{}-gram 언덕 오르기] 찾은 키: {} (점수 {:.1})", n, guess.key, guess.score);
+        println!("{}", guess.plaintext);
+        println!("-> 원문과 일치하는 글자: {:.1}%", 100.0 * correct as f64 / secret_text.chars().count() as f64);
+    }
+
+
+    println!("\n--- [실험 1-3] 비즈네르 암호 (키 글자마다 다른 시저 암호) ---");
+    let vigenere_text = "For three hundred years the Vigenere cipher was called the indecipherable cipher. \
+        Each letter of the message is shifted by a different amount, taken in turn from the letters of a short keyword, \
+        so the most common letter of the language no longer maps to a single symbol. \
+        Counting letters across the whole message therefore shows an almost flat picture. \
+        The weakness is that the keyword repeats. When the same word in the message meets the same part of the key, \
+        the same group of symbols appears again, and the distance between the two groups is a multiple of the key length. \
+        Once the attacker knows the length, he splits the message into columns, and every column is just a Caesar cipher \
+        that falls to the same letter counting that broke the very first secret alphabets.";
+    let vigenere_key = "SATOSHI";
+    let vigenere_encrypted = vigenere::encrypt(vigenere_text, vigenere_key).expect("키에 알파벳이 있음");
+    println!("암호문: {}", vigenere_encrypted);
+
+    // 1. 시저 해독기로는 전체 빈도가 평평해서 실패
+    let caesar_try = &break_caesar(&vigenere_encrypted, &ENGLISH)[0];
+    println!("\n[시저 해독기] 카이제곱 {:.1} (영어라면 수십 정도) -> 실패", caesar_try.score);
+
+    // 2. 카시스키 검사: 반복되는 3글자 묶음 사이 간격의 약수
+    let votes = vigenere::kasiski(&vigenere_encrypted);
+    let top: Vec<String> = votes.iter().take(5).map(|(length, count)| format!("{}({}표)", length, count)).collect();
+    println!("\n[카시스키 검사] 키 길이 후보: {}", top.join(", "));
+
+    // 3. 일치 지수: 키 길이로 열을 나누면 각 열은 영어처럼 보임
+    println!("\n[일치 지수] 영어 ≈ 0.066, 랜덤 ≈ 0.038");
+    for length in 1..=8 {
+        println!("  키 길이 {} -> 열 평균 IoC {:.4}", length, vigenere::column_ioc(&vigenere_encrypted, length));
+    }
+    let key_length = vigenere::estimate_key_length(&vigenere_encrypted).expect("키 길이 추정 실패");
+    println!("-> 추정 키 길이: {}", key_length);
+
+    // 4. 열마다 시저 해독
+    let recovered_key = vigenere::recover_key(&vigenere_encrypted, key_length).expect("키 길이는 1 이상");
+    assert_eq!(recovered_key, vigenere_key);
+    println!("\n[열별 빈도 분석] 복구한 키: {}", recovered_key);
+    println!("{}", vigenere::decrypt(&vigenere_encrypted, &recovered_key).expect("복구한 키는 알파벳"));
+    println!("-> 해커 왈: 키가 반복되는 순간 다중 치환도 결국 시저 암호 여러 개일 뿐! (빈도 분석 성공 🚨)");
+
+
+    println!("\n--- [실험 1-4] 고전 암호 모음 (같은 ClassicalCipher 트레이트) ---");
+    let ciphers: Vec<Box<dyn ClassicalCipher>> = vec![
+        Box::new(Caesar { shift: 3 }),
+        Box::new(Affine::new(5, 8).expect("5는 26과 서로소")),
+        Box::new(Vigenere::new("LEMON").expect("키워드가 있음")),
+        Box::new(Playfair::new("MONARCHY")),
+        Box::new(Hill::new([[3, 3], [2, 5]]).expect("행렬식 9는 26과 서로소")),
+        Box::new(ColumnarTransposition::new("ZEBRAS").expect("키워드가 있음")),
+        Box::new(Enigma::new([0, 1, 2], "AAA", "AAA", "AB CD").expect("올바른 에니그마 키")),
+    ];
+    let classic_text = "WEAREDISCOVEREDFLEEATONCE";
+    println!("평문: {}", classic_text);
+    for cipher in &ciphers {
+        let encrypted = cipher.encrypt(classic_text);
+        let decrypted = cipher.decrypt(&encrypted);
+        assert_eq!(decrypted, cipher.normalize(classic_text));
+        println!("  {:<24} -> {:<28} 복호화 ✅", cipher.name(), encrypted);
+    }
+    // 알파벳이 아닌 글자는 그대로, 대소문자도 제자리 (아무 문자열 왕복은 cargo test로 확인)
+    let playfair = Playfair::new("MONARCHY");
+    let mixed_text = "Meet me at the box, 10시!";
+    let mixed = playfair.encrypt(mixed_text);
+    assert_eq!(playfair.decrypt(&mixed), mixed_text);
+    println!("  {} -> {}", mixed_text, mixed);
+
+    println!("\n--- [실험 1-5] 에니그마: 글자를 누를 때마다 치환표가 바뀌는 기계 ---");
+    // 교과서 검증값: 로터 I-II-III, 링 AAA, 위치 AAA, 플러그 없음이면 AAAAA -> BDZGO
+    assert_eq!(Enigma::new([0, 1, 2], "AAA", "AAA", "").expect("올바른 에니그마 키").encrypt("AAAAA"), "BDZGO");
+
+    // 오늘의 키: 로터 II-III-I, 링 BUL, 시작 위치 DKR, 플러그 10쌍
+    let daily_key = Enigma::new([1, 2, 0], "BUL", "DKR", "AV BS CG DL FU HZ IN KM OW RX").expect("올바른 에니그마 키");
+    let weather = "WEATHERFORECASTNORTHSEAWINDFROMTHEWESTATFORCESIXRAINSPREADINGEASTWARDSDURINGTHEEVENING\
+        VISIBILITYPOORINFOGPATCHESCONVOYSHOULDWAITFORDAYLIGHTBEFORELEAVINGTHEHARBOUR";
+    let intercepted = daily_key.encrypt(weather);
+    assert_eq!(daily_key.decrypt(&intercepted), weather);
+    println!("키 설정  : {} / 플러그 AV BS CG DL FU HZ IN KM OW RX", daily_key.name());
+    println!("가로챈 암호문: {}", intercepted);
+
+    // 1. 시저처럼 빈도 분석? 글자마다 치환표가 달라서 빈도가 평평해짐
+    let enigma_letters = ENGLISH.normalize(&intercepted);
+    println!(
+        "\n[빈도 분석] 암호문 IoC {:.4} (영어 약 0.066, 완전 랜덤 0.038) -> 글자 빈도로는 못 뚫음",
+        frequency::index_of_coincidence(&enigma_letters, 26)
+    );
+
+    // 2. 크립: 일기예보는 매일 같은 머리말로 시작함 (블레츨리 파크가 실제로 노린 약점)
+    let crib = "WEATHERFORECASTNORTH";
+    let crib_positions = enigma::possible_crib_positions(&intercepted, crib);
+    assert!(crib_positions.contains(&0));
+    println!(
+        "[크립] '{}'를 놓아볼 수 있는 위치 {}곳 (자기 자신으로 암호화되는 글자가 생기는 위치는 탈락)",
+        crib,
+        crib_positions.len()
+    );
+
+    // 3. 봄브: 로터 순서 x 시작 위치 17,576가지를 모순 추적으로 걸러냄
+    // 1938년 12월 전까지 육군 에니그마 I의 로터는 I~III 3개뿐 -> 순서 6가지 (5개 60가지는 --release로 해볼 것)
+    let orders = enigma::rotor_orders(3);
+    let started = Instant::now();
+    let stops = enigma::bombe(&intercepted, crib, 0, &orders);
+    println!(
+        "[봄브] 로터 설정 {}가지 중 멈춘 곳 {}개 ({:.1}초)",
+        orders.len() * 26 * 26 * 26,
+        stops.len(),
+        started.elapsed().as_secs_f64()
+    );
+
+    // 4. 멈춘 곳마다 오른쪽 링과 나머지 플러그를 채워서 가장 영어다운 해독을 고름
+    let recovered = enigma::recover_key(&intercepted, &stops, &NgramModel::english(3)).expect("봄브가 한 번은 멈춰야 함");
+    assert_eq!(recovered.rotors(), [1, 2, 0]);
+    assert_eq!(recovered.cores(), [2, 16, 6]); // 창 위치 - 링 = D-B, K-U, R-L
+    assert_eq!(recovered.rings()[2], 11);
+    println!(
+        "\n[복구한 키] 로터 {} | 링 {} | 플러그 {}",
+        enigma::rotor_names(recovered.rotors()),
+        recovered.rings().iter().map(|&r| (r + b'A') as char).collect::<String>(),
+        recovered.plugs()
+    );
+    println!("(왼쪽/가운데 링은 로터가 넘어가는 시점에만 영향 -> 이 길이의 전문에서는 원래 BUL과 해독 결과가 같음)");
+    println!("{}", recovered.plaintext);
+    assert_eq!(recovered.plaintext, weather);
+    println!("-> 해커 왈: 자기 자신으로는 안 바뀐다 + 매일 같은 머리말 = 에니그마도 뚫림 (봄브 성공 🚨)");
+
+    println!("\n(직접 만든 암호문 분석: cargo run --bin frequency_attack -- report <파일>)");
+    println!("\n--------------------------------------------");
+
+    println!("--- [실험 2] 블록체인 기술 (SHA-256 해시) ---");
+
+    // 똑같은 데이터를 해시 함수에 넣음
+    let hash_result1 = get_hash(input);
+    println!("원본 데이터: {}", input);
+    println!("해시 결과 1: {}", hash_result1);
+
+
+    // 2-1. 쇄도 효과(Avalanche Effect) 테스트
+    // 원본에서 딱 한 글자만 바꿔봄 (마지막 'O' -> '!')
+    let input_modified = "HELLO HELLO HELL!";
+    let hash_result2 = get_hash(input_modified);
+
+    println!("\n[쇄도 효과 태스트] 원본에서 글자 하나만 바꿈 ('O' -> '!')");
+    println!("수정 데이터: {}", input_modified);
+    println!("해시 결과 2: {}", hash_result2);
+
+    println!("\n[분석]");
+    println!("1. 결과값 길이는 64자로 똑같음.");
+    println!("2. 하지만 결과 1과 결과 2는 완전히 다르게 생김.");
+    println!("-> 해커 왈: 입력값이 아주 조금 바뀌었는데 결과가 뒤집어졌네? 패턴을 못 찾겠다! (빈도 분석 불가 🔒)");
+
+    // 2-2. 눈으로 보지 말고 비트 단위로 세어보기
+    let changed_bits: u32 = hex::decode(&hash_result1)
+        .unwrap()
+        .iter()
+        .zip(hex::decode(&hash_result2).unwrap())
+        .map(|(a, b)| (a ^ b).count_ones())
+        .sum();
+    println!("\n[쇄도 효과 측정] 결과 1과 2를 비트로 비교: 256비트 중 {}비트 변경 ({:.1}%)", changed_bits, changed_bits as f64 / 2.56);
+
+    // 2-3. 랜덤 입력 500개 x 입력 비트 128개를 하나씩 뒤집어서 통계 내기 (약한 해시와 비교)
+    println!("랜덤 16바이트 입력 500개, 입력 비트 하나씩 뒤집기:");
+    let reports: Vec<AvalancheReport> = HASH_FUNCTIONS
+        .iter()
+        .map(|function| avalanche::measure(function, 16, 500, &mut rng))
+        .collect();
+    for report in &reports {
+        report.print_summary();
+    }
+    println!("(표본 수 때문에 생기는 SAC 편차 평균은 완벽한 해시라도 약 {:.3})", reports[0].sampling_noise());
+    for report in &reports {
+        report.print_histogram();
+    }
+    println!("\n-> SHA-256은 항상 절반 근처(좁은 종 모양), SAC 편차도 표본 잡음 수준");
+    println!("-> CRC32는 평균만 보면 50% 근처지만, 입력 비트마다 바뀌는 출력 비트가 항상 똑같아서 SAC 편차가 0.5 (선형 🚨)");
+    println!("-> djb2는 끝쪽 입력 비트가 출력 아래쪽만 건드려서 분포가 한쪽으로 쏠림 (패턴이 보임 🚨)");
+
+    println!("\n--------------------------------------------");
+    println!("--- [실험 3] 잘린 SHA-256으로 충돌/역상 찾기 ---");
+    // SHA-256의 앞 n비트만 쓰는 작은 해시로 n을 늘려가며 시도 횟수 측정 (n마다 5번 평균)
+    // 역상은 n=16까지만 (20비트만 해도 디버그 모드에서 한참 걸림, --release로 늘려볼 것)
+    let rows = collision::run_experiments(&[8, 12, 16, 20, 24, 28], 16, 5, &mut rng);
+    collision::print_table(&rows);
+    collision::print_plot(&rows);
+    println!("\n-> 충돌은 n이 4 늘 때마다 4배(2^(n/2)), 역상은 16배(2^n)씩 어려워짐");
+    println!("-> 로 + 플로이드는 생일 공격과 비슷한 횟수인데 표를 저장하지 않아서 메모리가 거의 안 듦");
+    println!("-> 진짜 SHA-256(n=256)은 충돌만 해도 2^128번: 해커 왈 '이건 못 뚫겠다' (🔒)");
+
+    println!("\n--------------------------------------------");
+    println!("--- [실험 4] 해시를 MAC으로 잘못 쓰면: 길이 확장 공격 ---");
+    // 서버: "비밀키를 앞에 붙여서 해시하면 아무도 태그를 못 만들겠지?" (MAC = SHA256(key || message))
+    let server_key = b"server_secret_key_2024";
+    let naive = NaiveMacServer::new(server_key);
+    let order = b"from=alice&to=bob&amount=100";
+    let order_tag = naive.tag(order);
+    println!("정상 주문  : {}", String::from_utf8_lossy(order));
+    println!("정상 태그  : {}", order_tag);
+
+    // 해커: 키는 모르지만 주문과 태그를 엿봄 -> 뒤에 "&to=mallory&amount=1000000"을 이어 붙이기
+    let suffix = b"&to=mallory&amount=1000000";
+    let (key_len, forged, forged_tag) =
+        length_extension::forge(order, &order_tag, suffix, |m, t| naive.verify(m, t)).expect("서버 태그는 SHA-256").expect("길이 확장 실패");
+    assert_eq!(key_len, server_key.len());
+    println!("\n[해커] 키 길이 {}바이트로 추측했을 때 서버가 통과시킴", key_len);
+    println!("위조 주문  : {}", forged.escape_ascii());
+    println!("위조 태그  : {}", forged_tag);
+    println!("-> 중간의 \\x80...는 SHA-256 패딩. 서버가 '&'로 나눠 읽으면 마지막 to/amount가 이김 (위조 성공 🚨)");
+
+    // HMAC으로 고치면: 같은 공격이 어떤 키 길이로도 통하지 않음
+    let hmac = HmacServer::new(server_key);
+    let hmac_tag = hmac.tag(order);
+    let hmac_forgery = length_extension::forge(order, &hmac_tag, suffix, |m, t| hmac.verify(m, t));
+    assert_eq!(hmac_forgery, Ok(None));
+    println!("\n[HMAC-SHA256] 태그: {}", hmac_tag);
+    println!("-> 키 길이 1~64를 다 넣어봐도 위조 실패. 바깥 해시가 내부 상태를 감춰서 이어 붙일 수가 없음 (🔒)");
+
+    println!("\n--------------------------------------------");
+    println!("--- [실험 5] 비밀번호 저장 방식 점검 (DB가 털렸다면?) ---");
+    let users = [
+        ("alice", "password1"),
+        ("bob", "qwer1234"),
+        ("carol", "qwer1234"),
+        ("dave", "Sunshine"),
+        ("erin", "correct horse battery staple"),
+    ];
+    let words = password_audit::dictionary();
+    println!("해커의 사전: 흔한 비밀번호 + 변형 = {}개", words.len());
+
+    // 5-1. 소금 없는 SHA-256: bob과 carol의 해시가 똑같아서 "같은 비밀번호"라는 게 바로 보임
+    let unsalted: Vec<StoredPassword> = users
+        .iter()
+        .map(|(user, password)| StoredPassword::new(user, password, Scheme::Sha256, &mut rng))
+        .collect();
+    println!("\n[SHA-256 그대로 저장한 DB]");
+    for stored in &unsalted {
+        println!("  {:<6} | {}", stored.user, stored.hash);
+    }
+
+    // 해커는 DB를 훔치기 전에 미리 레인보우 테이블을 만들어 둠 (한 번 만들면 모든 서버에 재사용)
+    // 체인끼리 합쳐지는 만큼 저장 줄 수는 줄고, 못 덮는 단어가 생김 (메모리 <-> 적중률 거래)
+    let (chain_count, chain_len) = (80, 40);
+    let rainbow = RainbowTable::build(&words, chain_count, chain_len).expect("사전이 비어 있지 않음");
+    println!(
+        "\n[레인보우 테이블] 체인 {}개 x 길이 {}: 저장은 {}줄뿐인데 사전 {}개 중 {}개를 덮음",
+        chain_count,
+        chain_len,
+        rainbow.stored_entries(),
+        words.len(),
+        rainbow.coverage()
+    );
+    let mut cracked = 0;
+    for stored in &unsalted {
+        match rainbow.lookup(&stored.hash) {
+            Some(password) => {
+                assert!(stored.verify(password));
+                cracked += 1;
+                println!("  {:<6} -> '{}' (🚨)", stored.user, password);
+            }
+            None => println!("  {:<6} -> 표에 없음", stored.user),
+        }
+    }
+    println!("-> 해시 계산 없이 표 조회만으로 {}명 뚫림", cracked);
+
+    // 5-2. 소금 + SHA-256: 같은 비밀번호도 해시가 달라지고, 미리 만든 표는 쓸모가 없어짐
+    let salted: Vec<StoredPassword> = users
+        .iter()
+        .map(|(user, password)| StoredPassword::new(user, password, Scheme::SaltedSha256, &mut rng))
+        .collect();
+    assert_ne!(salted[1].hash, salted[2].hash);
+    assert!(salted.iter().all(|stored| rainbow.lookup(&stored.hash).is_none()));
+    println!("\n[소금 + SHA-256] bob/carol 해시가 달라졌고, 레인보우 테이블 적중 0건 (🔒)");
+    println!("하지만 사용자마다 사전을 처음부터 다시 돌리면...");
+    let mut total_guesses = 0;
+    for stored in &salted {
+        match password_audit::dictionary_attack(stored, &words) {
+            Some((password, guesses)) => {
+                total_guesses += guesses;
+                println!("  {:<6} -> '{}' ({}번째 시도)", stored.user, password, guesses);
+            }
+            None => {
+                total_guesses += words.len();
+                println!("  {:<6} -> 사전에 없음 ({}번 시도)", stored.user, words.len());
+            }
+        }
+    }
+    println!("-> 총 {}번 해시: 소금은 '한 번에 다 뚫기'만 막을 뿐, 약한 비밀번호는 여전히 뚫림", total_guesses);
+
+    // 5-3. 일부러 느린 해시: 한 번 맞춰보는 비용 자체를 키움
+    // 디버그 모드라 실제 서버(--release)보다 몇십 배 느리게 나옴, 방식끼리의 비율을 볼 것
+    let schemes = [
+        Scheme::Sha256,
+        Scheme::SaltedSha256,
+        Scheme::Pbkdf2 { rounds: 10_000 },
+        Scheme::Scrypt { log_n: 12 },
+        Scheme::Argon2id { memory_kib: 8 * 1024, passes: 2 },
+    ];
+    let leaked_list = 14_344_391.0; // 실제로 유출된 비밀번호 목록(rockyou.txt)의 줄 수
+    let baseline = password_audit::cost_per_guess(schemes[0], Duration::from_millis(200)).as_secs_f64();
+    println!("\n{:<26} | {:>10} | {:>8} | 사용자 1명당 rockyou 전체", "저장 방식", "1회 비용", "배율");
+    for scheme in schemes {
+        let cost = password_audit::cost_per_guess(scheme, Duration::from_millis(200)).as_secs_f64();
+        println!(
+            "{:<26} | {:>10} | {:>7.0}x | {}",
+            scheme.name(),
+            password_audit::format_duration(cost),
+            cost / baseline,
+            password_audit::format_duration(cost * leaked_list)
+        );
+    }
+
+    // 느린 해시도 사전 맨 앞에 있는 비밀번호는 못 지킴 (시간을 벌어줄 뿐)
+    let slow = StoredPassword::new("frank", "123456", Scheme::Argon2id { memory_kib: 8 * 1024, passes: 2 }, &mut rng);
+    let (password, guesses) = password_audit::dictionary_attack(&slow, &words).expect("사전 첫 단어");
+    println!("\n[Argon2id] frank -> '{}' ({}번째 시도): 느린 해시도 '123456'은 못 지킴", password, guesses);
+    println!("-> 결론: 소금 + 느린 해시(Argon2id/scrypt/PBKDF2)로 저장하고, 사전에 있는 비밀번호는 가입 때 거절할 것");
+
+}
+
+// [도구 1] 고전 암0호 함수 (글자를 shift만큼 이동)
+// 실제 구현은 caesar::rotate 하나뿐 (영어 알파벳 26자 기준으로 밀기)
+fn caesar_cipher(text: &str, shift: u8) -> String {
+    rotate(text, shift as usize, &ENGLISH)
+}
+// [도구 1-1] 시저 암호 자동 해독 (점수 상위 3개 후보 출력)
+fn attack_caesar(ciphertext: &str, language: &Language) -> Vec<CaesarGuess> {
+    let guesses = break_caesar(ciphertext, language);
+    println!("  ({} 빈도표 기준)", language.name);
+    for guess in guesses.iter().take(3) {
+        println!("  키 {:>2}칸 | 카이제곱 {:>9.2} | {}", guess.shift, guess.score, guess.plaintext);
+    }
+    guesses
+}
+
+// [도구 2] 블록체인 해시 함수 (SHA-256)
+fn get_hash(text: impl AsRef<[u8]>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(text);
+    hex::encode(hasher.finalize())
+}
\ No newline at end of file
@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use argon2::Argon2;
+use rand::Rng;
+use sha2::Sha256;
+
+use crate::get_hash;
+
+// [도구 10] 비밀번호 저장 방식 점검
+// 서버 DB가 털렸다고 가정하고, 저장 방식마다 해커가 비밀번호를 얼마나 쉽게 되찾는지 비교
+// 1. SHA-256 그대로    : 같은 비밀번호 = 같은 해시 -> 미리 계산한 표 하나로 모든 서버의 DB를 한 번에 조회
+// 2. 소금 + SHA-256    : 사용자마다 랜덤 소금 -> 미리 계산한 표가 무용지물, 하지만 한 번 맞춰보는 비용은 여전히 1µs 수준
+// 3. PBKDF2 / scrypt / Argon2id : 일부러 느린(그리고 메모리를 먹는) 해시 -> 한 번 맞춰보는 비용 자체를 키움
+const SALT_LEN: usize = 16;
+const HASH_LEN: usize = 32;
+
+// 흔히 쓰이는 비밀번호 목록 (유출 사전의 축소판)
+const COMMON_PASSWORDS: &str = include_str!("../../data/common_passwords.txt");
+
+#[derive(Clone, Copy)]
+pub enum Scheme {
+    Sha256,
+    SaltedSha256,
+    Pbkdf2 { rounds: u32 },
+    Scrypt { log_n: u8 },
+    Argon2id { memory_kib: u32, passes: u32 },
+}
+
+impl Scheme {
+    pub fn name(&self) -> String {
+        match self {
+            Scheme::Sha256 => "SHA-256 (소금 없음)".to_string(),
+            Scheme::SaltedSha256 => "소금 + SHA-256".to_string(),
+            Scheme::Pbkdf2 { rounds } => format!("PBKDF2-SHA256 ({}회)", rounds),
+            Scheme::Scrypt { log_n } => format!("scrypt (n = 2^{})", log_n),
+            Scheme::Argon2id { memory_kib, passes } => format!("Argon2id ({}MiB, {}회)", memory_kib / 1024, passes),
+        }
+    }
+
+    fn uses_salt(&self) -> bool {
+        !matches!(self, Scheme::Sha256)
+    }
+
+    pub fn hash(&self, password: &str, salt: &[u8]) -> String {
+        let mut out = [0u8; HASH_LEN];
+        match *self {
+            Scheme::Sha256 => return get_hash(password),
+            Scheme::SaltedSha256 => return get_hash([salt, password.as_bytes()].concat()),
+            Scheme::Pbkdf2 { rounds } => pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, rounds, &mut out),
+            Scheme::Scrypt { log_n } => {
+                let params = scrypt::Params::new(log_n, 8, 1, HASH_LEN).expect("데모용 scrypt 파라미터는 항상 유효함");
+                scrypt::scrypt(password.as_bytes(), salt, &params, &mut out).expect("출력 길이는 항상 32바이트");
+            }
+            Scheme::Argon2id { memory_kib, passes } => {
+                let params = argon2::Params::new(memory_kib, passes, 1, Some(HASH_LEN))
+                    .expect("데모용 Argon2 파라미터는 항상 유효함");
+                Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+                    .hash_password_into(password.as_bytes(), salt, &mut out)
+                    .expect("소금 16바이트, 출력 32바이트는 항상 유효함");
+            }
+        }
+        hex::encode(out)
+    }
+}
+
+// DB에 저장되는 한 줄: 사용자, 방식, 소금, 해시 (비밀번호 자체는 없음)
+pub struct StoredPassword {
+    pub user: String,
+    pub scheme: Scheme,
+    pub salt: Vec<u8>,
+    pub hash: String,
+}
+
+impl StoredPassword {
+    pub fn new(user: &str, password: &str, scheme: Scheme, rng: &mut impl Rng) -> Self {
+        let mut salt = vec![0u8; if scheme.uses_salt() { SALT_LEN } else { 0 }];
+        rng.fill(salt.as_mut_slice());
+        let hash = scheme.hash(password, &salt);
+        StoredPassword { user: user.to_string(), scheme, salt, hash }
+    }
+
+    pub fn verify(&self, guess: &str) -> bool {
+        self.scheme.hash(guess, &self.salt) == self.hash
+    }
+}
+
+// 해커의 사전: 흔한 비밀번호마다 사람들이 자주 하는 변형(대문자 시작, 숫자/느낌표 붙이기)을 더함
+pub fn dictionary() -> Vec<String> {
+    let mut words = Vec::new();
+    for word in COMMON_PASSWORDS.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let mut capitalized = word.to_string();
+        capitalized[..1].make_ascii_uppercase();
+        words.push(word.to_string());
+        if capitalized != word {
+            words.push(capitalized);
+        }
+        words.push(format!("{}1", word));
+        words.push(format!("{}123", word));
+        words.push(format!("{}!", word));
+    }
+    words
+}
+
+// [공격 1] 사전 공격: 한 사용자의 소금으로 사전을 처음부터 맞춰봄 -> (비밀번호, 시도 횟수)
+pub fn dictionary_attack(stored: &StoredPassword, words: &[String]) -> Option<(String, usize)> {
+    words
+        .iter()
+        .position(|word| stored.verify(word))
+        .map(|index| (words[index].clone(), index + 1))
+}
+
+// [공격 2] 레인보우 테이블: 소금 없는 SHA-256 전용 사전 계산 표
+// 사전 전체(해시 -> 비밀번호)를 다 저장하는 대신 "체인"의 시작과 끝만 저장해서 메모리를 아낌
+//   단어 --SHA-256--> 해시 --축소 함수 R_0--> 단어 --SHA-256--> 해시 --R_1--> ... --> 끝 단어
+// 축소 함수를 체인 위치마다 다르게 해서 두 체인이 부딪혀도 통째로 합쳐지는 일을 줄임 (그래서 "무지개")
+pub struct RainbowTable<'a> {
+    words: &'a [String],
+    chain_len: usize,
+    ends: HashMap<usize, usize>, // 끝 단어 번호 -> 시작 단어 번호
+}
+
+impl<'a> RainbowTable<'a> {
+    // 사전이 비어 있으면 None (축소 함수가 고를 단어가 없음)
+    // 체인 수가 단어 수보다 많으면 단어마다 체인 하나로 줄임 (같은 단어에서 시작하는 체인은 똑같음)
+    pub fn build(words: &'a [String], chain_count: usize, chain_len: usize) -> Option<Self> {
+        if words.is_empty() {
+            return None;
+        }
+        let mut table = RainbowTable { words, chain_len, ends: HashMap::new() };
+        let chain_count = chain_count.min(words.len());
+        let step = words.len() / chain_count.max(1);
+        for start in (0..chain_count).map(|k| k * step) {
+            let end = table.walk(start, 0, chain_len);
+            table.ends.entry(end).or_insert(start);
+        }
+        Some(table)
+    }
+
+    pub fn stored_entries(&self) -> usize {
+        self.ends.len()
+    }
+
+    // 표가 실제로 덮는 단어 수 (체인끼리 합쳐지면 겹치는 구간은 한 번만 셈)
+    pub fn coverage(&self) -> usize {
+        let mut covered = vec![false; self.words.len()];
+        for &start in self.ends.values() {
+            let mut index = start;
+            for position in 0..self.chain_len {
+                covered[index] = true;
+                index = self.reduce(&get_hash(&self.words[index]), position);
+            }
+        }
+        covered.iter().filter(|&&c| c).count()
+    }
+
+    // 해시 하나 찾기: "이 해시가 체인의 position번째 칸이었다면?"을 뒤에서부터 가정해봄
+    pub fn lookup(&self, hash: &str) -> Option<&'a str> {
+        for position in (0..self.chain_len).rev() {
+            let mut index = self.reduce(hash, position);
+            index = self.walk(index, position + 1, self.chain_len);
+            let Some(&start) = self.ends.get(&index) else { continue };
+
+            // 끝이 맞으면 체인을 처음부터 다시 따라가서 진짜 단어를 확인 (다른 체인과 합쳐진 가짜 경보일 수 있음)
+            let mut index = start;
+            for position in 0..self.chain_len {
+                let word = &self.words[index];
+                let word_hash = get_hash(word);
+                if word_hash == hash {
+                    return Some(word);
+                }
+                index = self.reduce(&word_hash, position);
+            }
+        }
+        None
+    }
+
+    // 체인 위치 from..to 구간을 따라가서 도착한 단어 번호
+    fn walk(&self, mut index: usize, from: usize, to: usize) -> usize {
+        for position in from..to {
+            index = self.reduce(&get_hash(&self.words[index]), position);
+        }
+        index
+    }
+
+    // 축소 함수 R_position: 해시 앞 8바이트를 숫자로 보고 위치값을 더해 사전 번호로
+    fn reduce(&self, hash: &str, position: usize) -> usize {
+        let prefix = u64::from_str_radix(&hash[..16], 16).expect("해시는 항상 hex");
+        (prefix.wrapping_add(position as u64) % self.words.len() as u64) as usize
+    }
+}
+
+// 한 번 맞춰보는 비용: budget 동안 최대한 반복해서 평균 (느린 해시는 최소 1번)
+pub fn cost_per_guess(scheme: Scheme, budget: Duration) -> Duration {
+    let salt = [0u8; SALT_LEN];
+    let started = Instant::now();
+    let mut guesses = 0u32;
+    while guesses == 0 || started.elapsed() < budget {
+        scheme.hash(&format!("guess{}", guesses), &salt);
+        guesses += 1;
+    }
+    started.elapsed() / guesses
+}
+
+// 사람이 읽기 좋은 시간 단위로
+pub fn format_duration(seconds: f64) -> String {
+    const UNITS: [(f64, &str); 6] = [
+        (365.0 * 86_400.0, "년"),
+        (86_400.0, "일"),
+        (3_600.0, "시간"),
+        (60.0, "분"),
+        (1.0, "초"),
+        (0.001, "ms"),
+    ];
+    for (unit, name) in UNITS {
+        if seconds >= unit {
+            return format!("{:.1}{}", seconds / unit, name);
+        }
+    }
+    format!("{:.1}µs", seconds * 1e6)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    fn words(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("word{}", i)).collect()
+    }
+
+    #[test]
+    fn empty_dictionary_is_rejected() {
+        assert!(RainbowTable::build(&[], 10, 10).is_none());
+    }
+
+    #[test]
+    fn more_chains_than_words_is_clamped() {
+        let words = words(5);
+        let table = RainbowTable::build(&words, 100, 3).unwrap();
+        assert!(table.stored_entries() <= 5);
+        assert_eq!(RainbowTable::build(&words, 0, 3).unwrap().stored_entries(), 0);
+    }
+
+    #[test]
+    fn every_covered_word_is_found() {
+        let words = words(200);
+        let table = RainbowTable::build(&words, 20, 10).unwrap();
+        let found = words.iter().filter(|word| table.lookup(&get_hash(word)) == Some(word.as_str())).count();
+        assert_eq!(found, table.coverage());
+        assert!(table.lookup(&get_hash("not in the dictionary")).is_none());
+    }
+
+    #[test]
+    fn salt_hides_equal_passwords() {
+        let mut rng = StdRng::seed_from_u64(39);
+        let a = StoredPassword::new("a", "qwer1234", Scheme::SaltedSha256, &mut rng);
+        let b = StoredPassword::new("b", "qwer1234", Scheme::SaltedSha256, &mut rng);
+        assert_ne!(a.hash, b.hash);
+        assert!(a.verify("qwer1234") && b.verify("qwer1234") && !a.verify("qwer12345"));
+    }
+
+    #[test]
+    fn dictionary_attack_counts_guesses() {
+        let words = words(10);
+        let stored = StoredPassword::new("a", "word3", Scheme::Sha256, &mut StdRng::seed_from_u64(39));
+        assert_eq!(dictionary_attack(&stored, &words), Some(("word3".to_string(), 4)));
+        let stored = StoredPassword::new("a", "nope", Scheme::Sha256, &mut StdRng::seed_from_u64(39));
+        assert_eq!(dictionary_attack(&stored, &words), None);
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+
+use crate::frequency::{ENGLISH, chi_squared, index_of_coincidence};
+use crate::substitution::NgramModel;
+use crate::vigenere;
+
+// [도구 7] 아무 암호문 파일이나 넣으면 빈도 분석 보고서를 출력
+// 사용법: cargo run --bin frequency_attack -- report [파일]   (파일을 안 주면 표준 입력에서 읽음)
+const TOP_NGRAMS: usize = 10;
+const MIN_LETTERS: usize = 100; // 이보다 짧으면 통계가 믿을 만하지 않음
+
+pub fn run(path: Option<&str>) -> io::Result<()> {
+    let data = match path {
+        Some(path) if path != "-" => fs::read(path)?,
+        _ => {
+            let mut buffer = Vec::new();
+            io::stdin().read_to_end(&mut buffer)?;
+            buffer
+        }
+    };
+    print_report(&data);
+    Ok(())
+}
+
+pub fn print_report(data: &[u8]) {
+    let text = String::from_utf8_lossy(data);
+    let letters = ENGLISH.normalize(&text);
+    let letter_ratio = letters.len() as f64 / data.len().max(1) as f64;
+
+    println!("=== 빈도 분석 보고서 ===");
+    println!("전체 {}바이트, 알파벳 {}자 ({:.1}%)", data.len(), letters.len(), letter_ratio * 100.0);
+
+    println!("\n[1글자 빈도] (괄호 안은 영어 평균)");
+    let mut unigrams: Vec<(usize, usize)> = (0..26).map(|i| (i, letters.iter().filter(|&&l| l == i).count())).collect();
+    unigrams.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    let peak = unigrams[0].1.max(1);
+    for &(index, count) in unigrams.iter().filter(|(_, count)| *count > 0) {
+        let percent = 100.0 * count as f64 / letters.len() as f64;
+        println!(
+            "  {} {:>5.2}% ({:>5.2}%) {}",
+            ENGLISH.letters[index],
+            percent,
+            ENGLISH.frequencies[index],
+            "#".repeat(count * 40 / peak)
+        );
+    }
+
+    for n in [2, 3] {
+        let top = top_ngrams(&letters, n);
+        let shown: Vec<String> = top.iter().map(|(gram, count)| format!("{}:{}", gram, count)).collect();
+        println!("\n[{}글자 묶음 상위 {}개] {}", n, TOP_NGRAMS, shown.join("  "));
+    }
+
+    let stats = Statistics::measure(data, &letters);
+    println!("\n[통계]");
+    println!("  일치 지수(IoC)        : {:.4}  (영어 0.066, 랜덤 0.038)", stats.ioc);
+    println!("  바이트 엔트로피       : {:.3} 비트/바이트 (영어 문장 ≈ 4.2, 랜덤 = 8)", stats.byte_entropy);
+    println!("  알파벳 엔트로피       : {:.3} 비트/글자 (영어 ≈ 4.18, 랜덤 = {:.2})", stats.letter_entropy, 26f64.log2());
+    println!("  영어 빈도와의 거리    : {:.3}  (카이제곱 / 글자 수, 영어면 0.1 이하)", stats.chi);
+    println!("  영어 2글자 묶음 점수  : {:.3}  (글자당 로그 확률, 영어면 -1.1 근처)", stats.bigram_score);
+
+    println!("\n[추정] {}", guess_cipher_class(&text, letters.len(), letter_ratio, stats.byte_entropy, stats.ioc, stats.chi, stats.bigram_score));
+}
+
+// 보고서의 [통계] 항목들
+struct Statistics {
+    ioc: f64,
+    chi: f64,
+    byte_entropy: f64,
+    letter_entropy: f64,
+    bigram_score: f64,
+}
+
+impl Statistics {
+    fn measure(data: &[u8], letters: &[usize]) -> Self {
+        Statistics {
+            ioc: index_of_coincidence(letters, 26),
+            chi: chi_squared(letters, &ENGLISH) / letters.len().max(1) as f64,
+            byte_entropy: entropy(data.iter().map(|&b| b as usize), 256),
+            letter_entropy: entropy(letters.iter().copied(), 26),
+            bigram_score: bigram_score(letters),
+        }
+    }
+}
+
+// 암호 종류 추정 규칙
+// - 알파벳이 거의 없고 엔트로피가 높음 -> 현대 암호/압축 (랜덤처럼 보임)
+// - IoC가 영어 수준 + 빈도도 영어 그대로 + 글자 순서도 영어 -> 평문
+// - IoC가 영어 수준 + 빈도는 영어 그대로 + 순서만 엉망 -> 전치 암호 (글자는 그대로, 자리만 바뀜)
+// - IoC가 영어 수준 + 빈도가 다름 -> 단일 치환 (글자 이름만 바뀜, 분포 모양은 유지)
+// - IoC가 낮지만 어떤 길이로 열을 나누면 영어처럼 보임 -> 다중 치환 (비즈네르 등)
+// - 어떻게 나눠도 랜덤 수준 -> 랜덤처럼 보임 (일회용 패드, 현대 암호)
+fn guess_cipher_class(text: &str, letter_count: usize, letter_ratio: f64, byte_entropy: f64, ioc: f64, chi: f64, bigram_score: f64) -> String {
+    if letter_ratio < 0.5 {
+        return if byte_entropy > 7.0 {
+            "랜덤처럼 보임 (현대 블록/스트림 암호 또는 압축 데이터)".to_string()
+        } else {
+            "알파벳 문서가 아님 (바이너리 데이터)".to_string()
+        };
+    }
+    if letter_count < MIN_LETTERS {
+        return format!("판단 보류 (알파벳이 {}자뿐, 최소 {}자는 필요)", letter_count, MIN_LETTERS);
+    }
+    if ioc > 0.058 {
+        if chi > 0.2 {
+            "단일 치환 암호 (분포 모양은 영어인데 글자가 바뀜 -> 치환 해독기로)".to_string()
+        } else if bigram_score > -1.3 {
+            "평문 (암호화되지 않은 영어로 보임)".to_string()
+        } else {
+            "전치 암호 (글자 빈도는 영어 그대로, 순서만 섞임)".to_string()
+        }
+    } else {
+        match vigenere::estimate_key_length(text) {
+            Some(length) => format!("다중 치환 암호 (비즈네르 등, 추정 키 길이 {} -> 열마다 시저 해독)", length),
+            None => "랜덤처럼 보임 (일회용 패드 또는 아주 긴 키의 다중 치환)".to_string(),
+        }
+    }
+}
+
+fn top_ngrams(letters: &[usize], n: usize) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for window in letters.windows(n) {
+        let gram: String = window.iter().map(|&l| ENGLISH.letters[l]).collect();
+        *counts.entry(gram).or_insert(0) += 1;
+    }
+    let mut sorted: Vec<(String, usize)> = counts.into_iter().collect();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    sorted.truncate(TOP_NGRAMS);
+    sorted
+}
+
+// 섀넌 엔트로피: -sum p log2 p
+fn entropy(symbols: impl Iterator<Item = usize>, alphabet_size: usize) -> f64 {
+    let mut counts = vec![0usize; alphabet_size];
+    let mut total = 0usize;
+    for symbol in symbols {
+        counts[symbol] += 1;
+        total += 1;
+    }
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+fn bigram_score(letters: &[usize]) -> f64 {
+    let model = NgramModel::english(2);
+    let bytes: Vec<u8> = letters.iter().map(|&l| l as u8).collect();
+    model.score(&bytes) / letters.len().saturating_sub(1).max(1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entropy_of_uniform_and_constant_symbols() {
+        assert_eq!(entropy((0..256).map(|b| b % 16), 16), 4.0);
+        assert_eq!(entropy([3, 3, 3].into_iter(), 26), 0.0);
+        assert_eq!(entropy(std::iter::empty(), 26), 0.0);
+    }
+
+    #[test]
+    fn top_ngrams_are_sorted_by_count() {
+        let letters = ENGLISH.normalize("the then there");
+        assert_eq!(top_ngrams(&letters, 3)[0], ("THE".to_string(), 3));
+    }
+
+    #[test]
+    fn cipher_classes() {
+        let guess = |letter_ratio, byte_entropy, ioc, chi, bigram| guess_cipher_class("", 500, letter_ratio, byte_entropy, ioc, chi, bigram);
+        assert!(guess(0.1, 7.9, 0.0, 0.0, 0.0).starts_with("랜덤처럼"));
+        assert!(guess(0.1, 3.0, 0.0, 0.0, 0.0).starts_with("알파벳 문서가 아님"));
+        assert!(guess(0.9, 4.2, 0.066, 0.05, -1.1).starts_with("평문"));
+        assert!(guess(0.9, 4.2, 0.066, 0.05, -1.6).starts_with("전치 암호"));
+        assert!(guess(0.9, 4.2, 0.066, 0.9, -2.0).starts_with("단일 치환"));
+        assert!(guess_cipher_class("", 10, 0.9, 4.2, 0.066, 0.05, -1.1).starts_with("판단 보류"));
+    }
+
+    #[test]
+    fn vigenere_ciphertext_reports_its_key_length() {
+        let plaintext: String = include_str!("../../data/english_corpus.txt").chars().take(3000).collect();
+        let ciphertext = vigenere::encrypt(&plaintext, "LEMON").unwrap();
+        let letters = ENGLISH.normalize(&ciphertext);
+        let letter_ratio = letters.len() as f64 / ciphertext.len() as f64;
+        let stats = Statistics::measure(ciphertext.as_bytes(), &letters);
+        let guess = guess_cipher_class(&ciphertext, letters.len(), letter_ratio, stats.byte_entropy, stats.ioc, stats.chi, stats.bigram_score);
+        assert!(guess.starts_with("다중 치환 암호 (비즈네르 등, 추정 키 길이 5 "), "{}", guess);
+    }
+
+    #[test]
+    fn empty_and_binary_input_do_not_panic() {
+        print_report(b"");
+        print_report(&[0u8, 255, 17, 42]);
+    }
+}
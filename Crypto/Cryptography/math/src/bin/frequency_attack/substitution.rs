@@ -0,0 +1,202 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::alphabet::{letters, map_letters};
+
+// [도구 4] 단일 치환 암호 해독기 (알파벳 26자를 아무렇게나 섞은 키)
+// 키의 개수는 26! ≈ 4 x 10^26 이라서 시저처럼 전부 풀어보기는 불가능
+// 대신 "영어다움" 점수(n-gram 로그 확률)를 정의하고, 두 글자씩 바꿔가며 점수가 오르는 쪽으로 언덕 오르기
+const ENGLISH_CORPUS: &str = include_str!("../../data/english_corpus.txt");
+
+// 키: 평문 글자 i -> 암호문 글자 key[i] (0 = A)
+pub struct SubstitutionKey([u8; 26]);
+
+impl SubstitutionKey {
+    pub fn random(rng: &mut impl Rng) -> Self {
+        let mut key: [u8; 26] = std::array::from_fn(|i| i as u8);
+        key.shuffle(rng);
+        SubstitutionKey(key)
+    }
+
+    // 암호문 글자 -> 평문 글자 표
+    fn inverse(&self) -> [u8; 26] {
+        let mut inverse = [0u8; 26];
+        for (plain, &cipher) in self.0.iter().enumerate() {
+            inverse[cipher as usize] = plain as u8;
+        }
+        inverse
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.0.swap(a, b);
+    }
+}
+
+impl std::fmt::Display for SubstitutionKey {
+    // ABC...Z 각각이 어떤 글자로 바뀌는지
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let letters: String = self.0.iter().map(|&c| (b'A' + c) as char).collect();
+        write!(f, "{}", letters)
+    }
+}
+
+pub fn encrypt(text: &str, key: &SubstitutionKey) -> String {
+    substitute(text, &key.0)
+}
+
+pub fn decrypt(text: &str, key: &SubstitutionKey) -> String {
+    substitute(text, &key.inverse())
+}
+
+fn substitute(text: &str, table: &[u8; 26]) -> String {
+    map_letters(text, |letters| letters.iter().map(|&x| table[x as usize] as i32).collect())
+}
+
+// [n-gram 모델] 영어 글에서 "앞의 n-1글자 다음에 이 글자가 올 확률" (예: TIO 다음엔 N)
+// 학습용 글이 짧아서 한 번도 못 본 묶음이 많으니, 1글자~n글자 확률을 섞어서(보간) 0이 안 나오게 함
+pub struct NgramModel {
+    n: usize,
+    log_probs: Vec<f64>,
+}
+
+impl NgramModel {
+    pub fn english(n: usize) -> Self {
+        Self::train(ENGLISH_CORPUS, n)
+    }
+
+    pub fn train(corpus: &str, n: usize) -> Self {
+        let letters: Vec<u8> = letters(corpus);
+        // counts[k-1][묶음] = k글자 묶음이 나온 횟수
+        let counts: Vec<Vec<u32>> = (1..=n)
+            .map(|k| {
+                let mut table = vec![0u32; 26usize.pow(k as u32)];
+                for window in letters.windows(k) {
+                    table[Self::index(window)] += 1;
+                }
+                table
+            })
+            .collect();
+
+        // 1글자 확률에 조금, 나머지 차수에 골고루 가중치
+        let weights: Vec<f64> = (1..=n)
+            .map(|k| if n == 1 { 1.0 } else if k == 1 { 0.1 } else { 0.9 / (n - 1) as f64 })
+            .collect();
+        let total = letters.len().max(1) as f64;
+
+        let log_probs = (0..26usize.pow(n as u32))
+            .map(|window| {
+                let mut probability = 0.0;
+                for k in 1..=n {
+                    let suffix = window % 26usize.pow(k as u32); // 마지막 k글자
+                    let context = suffix / 26; // 그 앞의 k-1글자
+                    let context_count = if k == 1 { total } else { counts[k - 2][context] as f64 };
+                    if context_count > 0.0 {
+                        probability += weights[k - 1] * counts[k - 1][suffix] as f64 / context_count;
+                    }
+                }
+                probability.max(1e-6).log10()
+            })
+            .collect();
+        NgramModel { n, log_probs }
+    }
+
+    fn index(window: &[u8]) -> usize {
+        window.iter().fold(0, |acc, &letter| acc * 26 + letter as usize)
+    }
+
+    // 글자(0~25) 배열의 영어다움 점수: 클수록(0에 가까울수록) 영어 같음
+    pub fn score(&self, letters: &[u8]) -> f64 {
+        letters.windows(self.n).map(|window| self.log_probs[Self::index(window)]).sum()
+    }
+}
+
+pub struct SubstitutionGuess {
+    pub key: SubstitutionKey,
+    pub plaintext: String,
+    pub score: f64,
+}
+
+// 언덕 오르기 + 랜덤 재시작
+// 1. 랜덤 키에서 출발
+// 2. 글자 두 개를 바꿔보고 점수가 오르면 채택, 아니면 되돌림
+// 3. 어떤 교환으로도 안 오르면 (언덕 꼭대기) 재시작, 그동안 찾은 최고 키를 기억
+pub fn break_substitution(ciphertext: &str, model: &NgramModel, restarts: usize, rng: &mut impl Rng) -> SubstitutionGuess {
+    let cipher_letters: Vec<u8> = letters(ciphertext);
+    let score_of = |key: &SubstitutionKey| {
+        let inverse = key.inverse();
+        let plain: Vec<u8> = cipher_letters.iter().map(|&c| inverse[c as usize]).collect();
+        model.score(&plain)
+    };
+
+    let mut best_key = SubstitutionKey::random(rng);
+    let mut best_score = score_of(&best_key);
+
+    for _ in 0..restarts {
+        let mut key = SubstitutionKey::random(rng);
+        let mut score = score_of(&key);
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for a in 0..26 {
+                for b in (a + 1)..26 {
+                    key.swap(a, b);
+                    let candidate = score_of(&key);
+                    if candidate > score {
+                        score = candidate;
+                        improved = true;
+                    } else {
+                        key.swap(a, b);
+                    }
+                }
+            }
+        }
+        if score > best_score {
+            best_score = score;
+            best_key = key;
+        }
+    }
+
+    SubstitutionGuess {
+        plaintext: decrypt(ciphertext, &best_key),
+        key: best_key,
+        score: best_score,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    const TEXT: &str = "When you send coins to a friend, the network does not care who you are. \
+        It only checks that the signature matches the key that owns the coins. \
+        That is why losing the seed phrase means losing the money forever, \
+        and why anyone who copies it can quietly take everything without asking. \
+        Keep the seed offline, write it on paper, and never type it into a website that asks for it.";
+
+    #[test]
+    fn decrypt_reverses_encrypt() {
+        let key = SubstitutionKey::random(&mut StdRng::seed_from_u64(1));
+        let encrypted = encrypt("Hello, World!", &key);
+        assert_eq!(encrypted.len(), "Hello, World!".len());
+        assert_eq!(decrypt(&encrypted, &key), "Hello, World!");
+    }
+
+    #[test]
+    fn model_prefers_english() {
+        let model = NgramModel::english(3);
+        let score = |text: &str| model.score(&letters::<u8>(text));
+        assert!(score("the cat sat on the mat") > score("qzj xvk wqp zz jxq vzk"));
+    }
+
+    #[test]
+    fn hill_climbing_reads_a_long_ciphertext() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let key = SubstitutionKey::random(&mut rng);
+        let guess = break_substitution(&encrypt(TEXT, &key), &NgramModel::english(3), 10, &mut rng);
+        let correct = TEXT.chars().zip(guess.plaintext.chars()).filter(|(a, b)| a == b).count();
+        assert!(correct * 10 >= TEXT.len() * 9, "{}", guess.plaintext);
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::alphabet::letters;
+use crate::caesar::shift_char;
+use crate::frequency::{ENGLISH, chi_squared, index_of_coincidence};
+
+// [도구 5] 비즈네르 암호: 키 글자마다 다른 시저 암호를 돌아가며 적용 (다중 치환)
+// 키 "KEY" -> 1번째 글자는 K(10칸), 2번째는 E(4칸), 3번째는 Y(24칸), 4번째는 다시 K ...
+// 같은 'E'도 위치에 따라 다른 글자가 되니 빈도표가 평평해짐 -> "해독 불가능한 암호"라고 불렸음
+// 하지만 키 길이만 알아내면, 같은 키 글자로 잠긴 열(column)들은 그냥 시저 암호라서 하나씩 깨짐
+const MAX_KEY_LENGTH: usize = 20;
+const RANDOM_IOC: f64 = 1.0 / 26.0;
+const ENGLISH_IOC: f64 = 0.066;
+
+#[derive(Debug, PartialEq)]
+pub enum VigenereError {
+    NoKeyLetters,  // 키에 알파벳이 하나도 없음 (빈 키 포함)
+    ZeroKeyLength, // 키 길이 0으로는 열을 나눌 수 없음
+}
+
+impl fmt::Display for VigenereError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VigenereError::NoKeyLetters => write!(f, "키에 알파벳이 하나도 없습니다"),
+            VigenereError::ZeroKeyLength => write!(f, "키 길이는 1 이상이어야 합니다"),
+        }
+    }
+}
+
+pub fn encrypt(text: &str, key: &str) -> Result<String, VigenereError> {
+    shift_by_key(text, key, true)
+}
+
+pub fn decrypt(text: &str, key: &str) -> Result<String, VigenereError> {
+    shift_by_key(text, key, false)
+}
+
+// 키에서 알파벳만 골라 이동 칸수로 (A=0, B=1, ...)
+pub fn key_shifts(key: &str) -> Result<Vec<u8>, VigenereError> {
+    let shifts: Vec<u8> = letters(key);
+    if shifts.is_empty() {
+        return Err(VigenereError::NoKeyLetters);
+    }
+    Ok(shifts)
+}
+
+// 글자마다 키 글자만큼 시저 이동. 알파벳이 아닌 글자는 건너뛰고, 키는 알파벳이 나올 때만 한 칸씩 전진
+fn shift_by_key(text: &str, key: &str, forward: bool) -> Result<String, VigenereError> {
+    let shifts = key_shifts(key)?;
+    let mut position = 0;
+    let shifted = text.chars()
+        .map(|c| {
+            if ENGLISH.index_of(c).is_none() {
+                return c;
+            }
+            let shift = shifts[position % shifts.len()] as usize;
+            position += 1;
+            shift_char(c, if forward { shift } else { 26 - shift }, &ENGLISH)
+        })
+        .collect();
+    Ok(shifted)
+}
+
+// [카시스키 검사] 같은 3글자 묶음이 반복되면, 같은 평문이 같은 키 위치에 걸렸을 가능성이 큼
+// -> 반복 간격은 키 길이의 배수. 간격들을 가장 많이 나누어떨어지게 하는 길이가 후보
+pub fn kasiski(ciphertext: &str) -> Vec<(usize, usize)> {
+    let letters: Vec<usize> = letters(ciphertext);
+    let mut last_seen: HashMap<&[usize], usize> = HashMap::new();
+    let mut distances = Vec::new();
+    for (position, trigram) in letters.windows(3).enumerate() {
+        if let Some(previous) = last_seen.insert(trigram, position) {
+            distances.push(position - previous);
+        }
+    }
+
+    let mut votes: Vec<(usize, usize)> = (2..=MAX_KEY_LENGTH)
+        .map(|length| (length, distances.iter().filter(|&&d| d % length == 0).count()))
+        .collect();
+    votes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    votes
+}
+
+// [일치 지수로 키 길이 추정] 길이 L로 열을 나눴을 때 각 열이 영어(IoC ≈ 0.066)처럼 보이면 정답
+// 정답의 배수(2L, 3L...)도 높게 나오고, 약수(L/2)는 두 시저가 섞여서 어중간하게 나오므로
+// 최고값의 90% 이상인 길이 중 가장 짧은 것을 고름
+pub fn column_ioc(ciphertext: &str, key_length: usize) -> f64 {
+    if key_length == 0 {
+        return 0.0;
+    }
+    let letters: Vec<usize> = letters(ciphertext);
+    let columns = split_columns(&letters, key_length);
+    columns.iter().map(|column| index_of_coincidence(column, 26)).sum::<f64>() / key_length as f64
+}
+
+// 어떤 길이로 나눠도 영어처럼 안 보이면 None (비즈네르가 아니거나 키가 너무 긺)
+pub fn estimate_key_length(ciphertext: &str) -> Option<usize> {
+    let iocs: Vec<f64> = (1..=MAX_KEY_LENGTH).map(|length| column_ioc(ciphertext, length)).collect();
+    let best = iocs.iter().copied().fold(0.0, f64::max);
+    if best < (RANDOM_IOC + ENGLISH_IOC) / 2.0 {
+        return None;
+    }
+    iocs.iter().position(|&ioc| ioc >= 0.9 * best).map(|index| index + 1)
+}
+
+// 열마다 26가지 이동을 시도해서 카이제곱이 가장 작은 것 = 그 열의 키 글자
+pub fn recover_key(ciphertext: &str, key_length: usize) -> Result<String, VigenereError> {
+    if key_length == 0 {
+        return Err(VigenereError::ZeroKeyLength);
+    }
+    let letters: Vec<usize> = letters(ciphertext);
+    let key = split_columns(&letters, key_length)
+        .iter()
+        .map(|column| {
+            let best_shift = (0..26)
+                .min_by(|&a, &b| {
+                    let score = |shift: usize| {
+                        let shifted: Vec<usize> = column.iter().map(|&c| (c + 26 - shift) % 26).collect();
+                        chi_squared(&shifted, &ENGLISH)
+                    };
+                    score(a).total_cmp(&score(b))
+                })
+                .unwrap();
+            (b'A' + best_shift as u8) as char
+        })
+        .collect();
+    Ok(key)
+}
+
+// key_length는 1 이상 (호출하는 쪽에서 검사)
+fn split_columns(letters: &[usize], key_length: usize) -> Vec<Vec<usize>> {
+    let mut columns = vec![Vec::new(); key_length];
+    for (position, &letter) in letters.iter().enumerate() {
+        columns[position % key_length].push(letter);
+    }
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "For three hundred years the Vigenere cipher was called the indecipherable cipher. \
+        Each letter of the message is shifted by a different amount, taken in turn from the letters of a short keyword, \
+        so the most common letter of the language no longer maps to a single symbol. \
+        Counting letters across the whole message therefore shows an almost flat picture. \
+        The weakness is that the keyword repeats.";
+
+    #[test]
+    fn encrypts_a_known_vector() {
+        // 교과서 예제: ATTACKATDAWN + LEMON
+        assert_eq!(encrypt("ATTACK AT DAWN", "LEMON").unwrap(), "LXFOPV EF RNHR");
+        assert_eq!(decrypt("LXFOPV EF RNHR", "lemon").unwrap(), "ATTACK AT DAWN");
+    }
+
+    #[test]
+    fn keys_without_letters_are_rejected() {
+        assert_eq!(encrypt("hello", ""), Err(VigenereError::NoKeyLetters));
+        assert_eq!(decrypt("hello", "123 !"), Err(VigenereError::NoKeyLetters));
+        assert_eq!(encrypt("hello", "K-3Y").unwrap(), encrypt("hello", "KY").unwrap());
+    }
+
+    #[test]
+    fn zero_key_length_is_rejected() {
+        assert_eq!(recover_key("ABCDEF", 0), Err(VigenereError::ZeroKeyLength));
+        assert_eq!(column_ioc("ABCDEF", 0), 0.0);
+    }
+
+    #[test]
+    fn recovers_the_key_of_a_known_ciphertext() {
+        let ciphertext = encrypt(TEXT, "SATOSHI").unwrap();
+        let key_length = estimate_key_length(&ciphertext).unwrap();
+        assert_eq!(key_length, 7);
+        let key = recover_key(&ciphertext, key_length).unwrap();
+        assert_eq!(key, "SATOSHI");
+        assert_eq!(decrypt(&ciphertext, &key).unwrap(), TEXT);
+    }
+
+    #[test]
+    fn plain_english_has_key_length_one() {
+        assert_eq!(estimate_key_length(TEXT), Some(1));
+        assert_eq!(recover_key(TEXT, 1).unwrap(), "A");
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::HDWallet;
+
+// [체인 분석 시뮬레이터] "주소가 다 다르게 생겼으니 추적 불가"가 정말일까?
+// 블록체인 분석 회사들이 쓰는 대표 휴리스틱 두 개:
+// 1. 공통 입력 소유 (common-input-ownership): 한 거래의 입력들은 모두 같은 사람 것
+//    (서명하려면 비밀키가 다 있어야 하니까)
+// 2. 거스름돈 주소 (change-address): 출력이 2개인데 하나는 딱 떨어지는 금액(결제),
+//    하나는 자투리 금액이면서 처음 보는 주소 -> 자투리 쪽이 보낸 사람의 거스름돈
+const FEE: u64 = 1_234;
+
+pub struct Tx {
+    pub inputs: Vec<String>,
+    pub outputs: Vec<(String, u64)>,
+}
+
+#[derive(Clone, Copy)]
+pub enum SpendingHabit {
+    CoSpend,     // 잔돈 UTXO 여러 개를 한 번에 모아서 결제 (대부분의 지갑 기본 동작)
+    CoinControl, // 결제할 때마다 UTXO 하나만 골라서 씀
+}
+
+pub struct Simulation {
+    pub txs: Vec<Tx>,
+    pub wallet_addresses: Vec<String>, // 시뮬레이션 중 실제로 사용된 지갑 주소 (Index 1부터)
+    pub chicken_tx: Option<usize>,     // 치킨 결제 거래 번호 (잔고가 모자라 못 샀으면 None)
+}
+
+// 가상의 거래 기록 만들기: 외부에서 8번 입금 받고, 6번 결제 (거스름돈은 항상 새 주소로)
+// 잔고가 모자란 결제는 건너뜀
+pub fn simulate(wallet: &HDWallet, habit: SpendingHabit, seed: u64) -> Simulation {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut txs = Vec::new();
+    let mut wallet_addresses = Vec::new();
+    let mut utxos: Vec<(String, u64)> = Vec::new();
+
+    let fresh_address = |wallet_addresses: &mut Vec<String>| {
+        let address = wallet.derive_address(&[wallet_addresses.len() as u32 + 1]);
+        wallet_addresses.push(address.clone());
+        address
+    };
+
+    // 1. 입금: 외부 사람이 자기 코인에서 우리 주소로 보내고 자기 거스름돈을 받아감
+    for payer in 0..8 {
+        let address = fresh_address(&mut wallet_addresses);
+        let amount = rng.gen_range(5..50) * 10_000;
+        txs.push(Tx {
+            inputs: vec![format!("ext_payer_{}", payer)],
+            outputs: vec![
+                (address.clone(), amount),
+                (format!("ext_change_{}", payer), rng.gen_range(1..1_000) * 1_000 + 567),
+            ],
+        });
+        utxos.push((address, amount));
+    }
+
+    // 2. 결제: 가게에는 딱 떨어지는 금액, 나머지는 새 주소로 거스름돈
+    let mut chicken_tx = None;
+    for (k, shop) in ["치킨", "편의점", "커피", "책", "영화", "택시"].iter().enumerate() {
+        let amount = rng.gen_range(1..15) * 10_000;
+        let Some(spent) = select_coins(&mut utxos, amount + FEE, habit) else {
+            continue;
+        };
+        let total: u64 = spent.iter().map(|(_, value)| value).sum();
+
+        let change_address = fresh_address(&mut wallet_addresses);
+        let change = total - amount - FEE;
+        if *shop == "치킨" {
+            chicken_tx = Some(txs.len());
+        }
+        txs.push(Tx {
+            inputs: spent.into_iter().map(|(address, _)| address).collect(),
+            outputs: vec![(format!("shop_{}_{}", shop, k), amount), (change_address.clone(), change)],
+        });
+        utxos.push((change_address, change));
+    }
+
+    Simulation {
+        txs,
+        wallet_addresses,
+        chicken_tx,
+    }
+}
+
+// 잔고를 다 모아도 모자라면 None (UTXO는 그대로 남겨둠)
+fn select_coins(utxos: &mut Vec<(String, u64)>, target: u64, habit: SpendingHabit) -> Option<Vec<(String, u64)>> {
+    if utxos.iter().map(|(_, value)| value).sum::<u64>() < target {
+        return None;
+    }
+    utxos.sort_by_key(|(_, value)| *value);
+    if let SpendingHabit::CoinControl = habit {
+        // 혼자서 결제 금액을 감당할 수 있는 가장 작은 UTXO 하나
+        if let Some(pos) = utxos.iter().position(|(_, value)| *value >= target) {
+            return Some(vec![utxos.remove(pos)]);
+        }
+    }
+    // 작은 것부터 모아서 금액을 채움 (잔돈 정리)
+    let mut spent = Vec::new();
+    let mut total = 0;
+    while total < target {
+        let coin = utxos.remove(0);
+        total += coin.1;
+        spent.push(coin);
+    }
+    Some(spent)
+}
+
+// [클러스터링] 같은 주인으로 보이는 주소끼리 유니온-파인드로 묶기
+pub struct Clusters {
+    ids: HashMap<String, usize>,
+    parent: Vec<usize>,
+}
+
+impl Clusters {
+    pub fn build(txs: &[Tx], use_change_heuristic: bool) -> Self {
+        let mut clusters = Clusters {
+            ids: HashMap::new(),
+            parent: Vec::new(),
+        };
+        let mut seen: HashSet<&str> = HashSet::new();
+
+        for tx in txs {
+            // 입력이 없는 거래(코인베이스)는 묶을 주인이 없음
+            if let Some((first, rest)) = tx.inputs.split_first() {
+                // 휴리스틱 1: 입력끼리 전부 묶기
+                for input in rest {
+                    clusters.union(first, input);
+                }
+
+                // 휴리스틱 2: 자투리 금액 + 처음 보는 주소 = 거스름돈 -> 입력과 묶기
+                if use_change_heuristic && tx.outputs.len() == 2 {
+                    let odd: Vec<&(String, u64)> = tx.outputs.iter().filter(|(_, value)| value % 1_000 != 0).collect();
+                    if let [(change, _)] = odd.as_slice()
+                        && !seen.contains(change.as_str())
+                    {
+                        clusters.union(first, change);
+                    }
+                }
+            }
+
+            seen.extend(tx.inputs.iter().map(String::as_str));
+            seen.extend(tx.outputs.iter().map(|(address, _)| address.as_str()));
+        }
+        clusters
+    }
+
+    pub fn same_owner(&mut self, a: &str, b: &str) -> bool {
+        let (a, b) = (self.id(a), self.id(b));
+        self.find(a) == self.find(b)
+    }
+
+    // 주어진 주소들이 몇 개의 덩어리로 나뉘는지, 가장 큰 덩어리에 몇 개가 들어있는지
+    pub fn summarize(&mut self, addresses: &[String]) -> (usize, usize) {
+        let mut sizes: HashMap<usize, usize> = HashMap::new();
+        for address in addresses {
+            let id = self.id(address);
+            *sizes.entry(self.find(id)).or_insert(0) += 1;
+        }
+        let largest = sizes.values().copied().max().unwrap_or(0);
+        (sizes.len(), largest)
+    }
+
+    fn id(&mut self, address: &str) -> usize {
+        if let Some(&id) = self.ids.get(address) {
+            return id;
+        }
+        let id = self.parent.len();
+        self.parent.push(id);
+        self.ids.insert(address.to_string(), id);
+        id
+    }
+
+    fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]]; // 경로 압축
+            x = self.parent[x];
+        }
+        x
+    }
+
+    fn union(&mut self, a: &str, b: &str) {
+        let (a, b) = (self.id(a), self.id(b));
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        self.parent[root_a] = root_b;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(inputs: &[&str], outputs: &[(&str, u64)]) -> Tx {
+        Tx {
+            inputs: inputs.iter().map(|input| input.to_string()).collect(),
+            outputs: outputs.iter().map(|(address, value)| (address.to_string(), *value)).collect(),
+        }
+    }
+
+    #[test]
+    fn common_inputs_are_merged() {
+        let txs = [tx(&["a", "b"], &[("shop", 10_000)]), tx(&["b", "c"], &[("shop", 20_000)]), tx(&["d"], &[("shop", 5_000)])];
+        let mut clusters = Clusters::build(&txs, false);
+        assert!(clusters.same_owner("a", "c"));
+        assert!(!clusters.same_owner("a", "d"));
+        let addresses: Vec<String> = ["a", "b", "c", "d"].iter().map(|a| a.to_string()).collect();
+        assert_eq!(clusters.summarize(&addresses), (2, 3));
+    }
+
+    #[test]
+    fn odd_fresh_output_is_taken_as_change() {
+        let txs = [
+            tx(&["a"], &[("shop", 30_000), ("change", 1_234)]),
+            tx(&["b"], &[("shop", 40_000), ("change", 5_678)]), // 자투리지만 이미 본 주소라서 거스름돈이 아님
+        ];
+        let mut without = Clusters::build(&txs, false);
+        assert!(!without.same_owner("a", "change"));
+
+        let mut with = Clusters::build(&txs, true);
+        assert!(with.same_owner("a", "change"));
+        assert!(!with.same_owner("b", "change"));
+        assert!(!with.same_owner("a", "shop"));
+    }
+
+    #[test]
+    fn co_spending_leaks_more_than_coin_control() {
+        let wallet = HDWallet::new("test seed");
+        let largest = |habit| {
+            let sim = simulate(&wallet, habit, 42);
+            Clusters::build(&sim.txs, false).summarize(&sim.wallet_addresses).1
+        };
+        assert!(largest(SpendingHabit::CoSpend) > largest(SpendingHabit::CoinControl));
+    }
+
+    #[test]
+    fn coinbase_without_inputs_is_skipped() {
+        let txs = [tx(&[], &[("miner", 625_000_001)]), tx(&["a"], &[("shop", 10_000), ("change", 1_234)])];
+        let mut clusters = Clusters::build(&txs, true);
+        assert!(clusters.same_owner("a", "change"));
+        assert!(!clusters.same_owner("miner", "a"));
+    }
+
+    #[test]
+    fn short_balance_selects_nothing() {
+        let mut utxos = vec![("a".to_string(), 30_000), ("b".to_string(), 20_000)];
+        for habit in [SpendingHabit::CoSpend, SpendingHabit::CoinControl] {
+            assert!(select_coins(&mut utxos, 50_001, habit).is_none());
+            assert_eq!(utxos.len(), 2);
+        }
+        assert_eq!(select_coins(&mut utxos, 50_000, SpendingHabit::CoSpend).map(|spent| spent.len()), Some(2));
+        assert!(utxos.is_empty());
+    }
+}
@@ -0,0 +1,295 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use aes::cipher::{KeyIvInit, StreamCipher};
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use super::HDWallet;
+
+// [키스토어] 이더리움 keystore v3와 비슷한 모양의 암호화 지갑 파일
+// 비밀번호 --(scrypt)--> 파생키 64바이트
+//   [0..16]  : AES-128-CTR 암호화 키 (시드를 잠그는 자물쇠)
+//   [16..32] : HMAC-SHA256 키 (파일이 조작됐는지 확인하는 봉인 스티커)
+//   [32..64] : 비밀번호 확인용 (틀린 비밀번호와 조작된 파일을 구분)
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+type HmacSha256 = Hmac<Sha256>;
+
+const KEYSTORE_VERSION: u32 = 3;
+const CIPHER_NAME: &str = "aes-128-ctr";
+const KDF_NAME: &str = "scrypt";
+const DK_LEN: usize = 64;
+
+// 파일에 적힌 scrypt 파라미터는 아무나 고칠 수 있으므로 상한을 둠
+// n = 2^30 같은 값을 넣은 파일 하나로 메모리(128 * r * n 바이트)를 다 잡아먹게 할 수 있음
+const MAX_LOG_N: u32 = 20; // 2^20 = r 8 기준 1GiB
+const MAX_R: u32 = 8;
+const MAX_P: u32 = 4;
+
+#[derive(Debug)]
+pub enum KeystoreError {
+    WrongPassword,     // 비밀번호가 틀림
+    Tampered,          // 비밀번호는 맞는데 암호문/IV가 바뀌어 있음
+    Malformed(String), // 파일 형식 자체가 이상함
+    Io(std::io::Error),
+}
+
+impl fmt::Display for KeystoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeystoreError::WrongPassword => write!(f, "비밀번호가 틀렸습니다"),
+            KeystoreError::Tampered => write!(f, "키스토어 파일이 조작되었습니다 (MAC 불일치)"),
+            KeystoreError::Malformed(reason) => write!(f, "키스토어 형식 오류: {}", reason),
+            KeystoreError::Io(err) => write!(f, "파일 입출력 오류: {}", err),
+        }
+    }
+}
+
+impl From<std::io::Error> for KeystoreError {
+    fn from(err: std::io::Error) -> Self {
+        KeystoreError::Io(err)
+    }
+}
+
+// scrypt 난이도 설정: n이 클수록 해커가 비밀번호 하나 맞춰보는 데 드는 시간/메모리가 커짐
+#[derive(Clone, Copy)]
+pub struct KdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl KdfParams {
+    // 데모용 가벼운 설정 (n = 2^12)
+    // 실제 이더리움 지갑은 n = 2^18을 써서 한 번 여는 데 1초 가까이 걸리게 만듦
+    pub fn light() -> Self {
+        KdfParams { log_n: 12, r: 8, p: 1 }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Keystore {
+    version: u32,
+    id: String,
+    crypto: CryptoSection,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CryptoSection {
+    cipher: String,
+    cipherparams: CipherParams,
+    ciphertext: String,
+    kdf: String,
+    kdfparams: ScryptParams,
+    mac: String,
+    pwcheck: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CipherParams {
+    iv: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ScryptParams {
+    dklen: usize,
+    n: u64,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+impl Keystore {
+    // 1. 잠그기: 비밀번호로 마스터 시드를 암호화
+    pub fn encrypt(seed: &[u8], password: &str, params: KdfParams) -> Self {
+        let mut rng = rand::thread_rng();
+        let mut salt = [0u8; 32];
+        let mut iv = [0u8; 16];
+        let mut id = [0u8; 16];
+        rng.fill(&mut salt);
+        rng.fill(&mut iv);
+        rng.fill(&mut id);
+
+        let dk = derive_key(password, &salt, params.log_n, params.r, params.p)
+            .expect("데모용 scrypt 파라미터는 항상 유효함");
+
+        let mut ciphertext = seed.to_vec();
+        let mut cipher = Aes128Ctr::new(dk[..16].into(), (&iv).into());
+        cipher.apply_keystream(&mut ciphertext);
+
+        Keystore {
+            version: KEYSTORE_VERSION,
+            id: format_uuid(&id),
+            crypto: CryptoSection {
+                cipher: CIPHER_NAME.to_string(),
+                cipherparams: CipherParams { iv: hex::encode(iv) },
+                ciphertext: hex::encode(&ciphertext),
+                kdf: KDF_NAME.to_string(),
+                kdfparams: ScryptParams {
+                    dklen: DK_LEN,
+                    n: 1u64 << params.log_n,
+                    r: params.r,
+                    p: params.p,
+                    salt: hex::encode(salt),
+                },
+                mac: hex::encode(compute_mac(&dk[16..32], &iv, &ciphertext)),
+                pwcheck: hex::encode(Sha256::digest(&dk[32..64])),
+            },
+        }
+    }
+
+    // 2. 열기: 비밀번호 확인 -> MAC(봉인) 확인 -> 복호화 순서
+    pub fn decrypt(&self, password: &str) -> Result<Vec<u8>, KeystoreError> {
+        let crypto = &self.crypto;
+        if self.version != KEYSTORE_VERSION {
+            return Err(KeystoreError::Malformed(format!("지원하지 않는 버전 {}", self.version)));
+        }
+        if crypto.cipher != CIPHER_NAME || crypto.kdf != KDF_NAME {
+            return Err(KeystoreError::Malformed(format!(
+                "지원하지 않는 알고리즘 {}/{}",
+                crypto.cipher, crypto.kdf
+            )));
+        }
+
+        let kdf = &crypto.kdfparams;
+        if kdf.dklen != DK_LEN || !kdf.n.is_power_of_two() || kdf.n < 2 {
+            return Err(KeystoreError::Malformed("scrypt 파라미터가 잘못됨".to_string()));
+        }
+        if kdf.n.trailing_zeros() > MAX_LOG_N || kdf.r == 0 || kdf.r > MAX_R || kdf.p == 0 || kdf.p > MAX_P {
+            return Err(KeystoreError::Malformed(format!(
+                "scrypt 파라미터가 허용 범위를 넘음 (n <= 2^{}, r <= {}, p <= {})",
+                MAX_LOG_N, MAX_R, MAX_P
+            )));
+        }
+        let salt = decode_hex("salt", &kdf.salt)?;
+        let iv = decode_hex("iv", &crypto.cipherparams.iv)?;
+        let mut ciphertext = decode_hex("ciphertext", &crypto.ciphertext)?;
+        let mac = decode_hex("mac", &crypto.mac)?;
+        let pwcheck = decode_hex("pwcheck", &crypto.pwcheck)?;
+        if iv.len() != 16 {
+            return Err(KeystoreError::Malformed("IV는 16바이트여야 함".to_string()));
+        }
+
+        let dk = derive_key(password, &salt, kdf.n.trailing_zeros() as u8, kdf.r, kdf.p)?;
+
+        // 비밀번호 확인값이 다르면 -> 틀린 비밀번호
+        // 비교는 상수 시간으로: 몇 번째 바이트에서 틀렸는지가 응답 시간으로 새지 않게
+        if !bool::from(Sha256::digest(&dk[32..64]).as_slice().ct_eq(&pwcheck)) {
+            return Err(KeystoreError::WrongPassword);
+        }
+
+        // 비밀번호는 맞는데 봉인이 깨졌으면 -> 누군가 파일을 건드림
+        if !bool::from(compute_mac(&dk[16..32], &iv, &ciphertext).ct_eq(&mac)) {
+            return Err(KeystoreError::Tampered);
+        }
+
+        let mut cipher = Aes128Ctr::new(dk[..16].into(), iv.as_slice().into());
+        cipher.apply_keystream(&mut ciphertext);
+        Ok(ciphertext)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), KeystoreError> {
+        let json = serde_json::to_string_pretty(self).expect("키스토어는 항상 JSON으로 변환 가능");
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, KeystoreError> {
+        let json = fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(|err| KeystoreError::Malformed(err.to_string()))
+    }
+}
+
+impl HDWallet {
+    // 마스터 시드를 비밀번호로 잠가서 파일로 저장
+    pub fn save_encrypted(&self, path: &Path, password: &str, params: KdfParams) -> Result<(), KeystoreError> {
+        Keystore::encrypt(self.master_seed.as_bytes(), password, params).save(path)
+    }
+
+    // 파일에서 지갑 복구 (비밀번호가 틀리거나 파일이 조작되면 에러)
+    pub fn load_encrypted(path: &Path, password: &str) -> Result<Self, KeystoreError> {
+        let seed = Keystore::load(path)?.decrypt(password)?;
+        let seed = String::from_utf8(seed)
+            .map_err(|_| KeystoreError::Malformed("시드가 UTF-8 문자열이 아님".to_string()))?;
+        Ok(HDWallet::new(&seed))
+    }
+}
+
+fn derive_key(password: &str, salt: &[u8], log_n: u8, r: u32, p: u32) -> Result<[u8; DK_LEN], KeystoreError> {
+    let params = scrypt::Params::new(log_n, r, p, DK_LEN)
+        .map_err(|_| KeystoreError::Malformed("scrypt 파라미터가 잘못됨".to_string()))?;
+    let mut dk = [0u8; DK_LEN];
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut dk)
+        .map_err(|_| KeystoreError::Malformed("scrypt 출력 길이가 잘못됨".to_string()))?;
+    Ok(dk)
+}
+
+fn compute_mac(mac_key: &[u8], iv: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(mac_key).expect("HMAC은 어떤 키 길이든 받음");
+    mac.update(iv);
+    mac.update(ciphertext);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>, KeystoreError> {
+    hex::decode(value).map_err(|_| KeystoreError::Malformed(format!("{} 필드가 hex가 아님", field)))
+}
+
+// 랜덤 16바이트를 UUID v4 모양(8-4-4-4-12)으로 표시
+fn format_uuid(bytes: &[u8; 16]) -> String {
+    let mut b = *bytes;
+    b[6] = (b[6] & 0x0f) | 0x40;
+    b[8] = (b[8] & 0x3f) | 0x80;
+    let h = hex::encode(b);
+    format!("{}-{}-{}-{}-{}", &h[0..8], &h[8..12], &h[12..16], &h[16..20], &h[20..32])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Keystore {
+        Keystore::encrypt(b"test seed phrase", "hunter2", KdfParams { log_n: 4, r: 8, p: 1 })
+    }
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(sample().decrypt("hunter2").unwrap(), b"test seed phrase");
+    }
+
+    #[test]
+    fn wrong_password_is_rejected() {
+        assert!(matches!(sample().decrypt("hunter3"), Err(KeystoreError::WrongPassword)));
+    }
+
+    #[test]
+    fn tampered_ciphertext_is_detected() {
+        let mut keystore = sample();
+        let mut ciphertext = hex::decode(&keystore.crypto.ciphertext).unwrap();
+        ciphertext[0] ^= 1;
+        keystore.crypto.ciphertext = hex::encode(ciphertext);
+        assert!(matches!(keystore.decrypt("hunter2"), Err(KeystoreError::Tampered)));
+    }
+
+    #[test]
+    fn oversized_kdf_params_are_rejected() {
+        for (n, r, p) in [(1u64 << 30, 8, 1), (1 << 4, 1 << 20, 1), (1 << 4, 8, 1 << 20), (1 << 4, 0, 1)] {
+            let mut keystore = sample();
+            keystore.crypto.kdfparams.n = n;
+            keystore.crypto.kdfparams.r = r;
+            keystore.crypto.kdfparams.p = p;
+            assert!(matches!(keystore.decrypt("hunter2"), Err(KeystoreError::Malformed(_))));
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let json = serde_json::to_string(&sample()).unwrap();
+        let keystore: Keystore = serde_json::from_str(&json).unwrap();
+        assert_eq!(keystore.decrypt("hunter2").unwrap(), b"test seed phrase");
+    }
+}
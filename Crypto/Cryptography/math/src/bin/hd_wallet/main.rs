@@ -0,0 +1,263 @@
+mod chain_analysis;
+mod keystore;
+mod recovery;
+mod shamir;
+mod signing;
+
+use sha2::{Sha256, Digest};
+use chain_analysis::{Clusters, SpendingHabit, simulate};
+use keystore::{KdfParams, KeystoreError};
+use recovery::{Ledger, recover_funds};
+use shamir::{Share, ShamirError};
+use signing::{Scheme, Transaction, address_of, verify_message};
+
+// [지갑 구조체] 마치 주민등록증 발급기 같은 역할
+struct HDWallet{
+    master_seed: String,
+}
+
+impl HDWallet {
+    // 1. 지갑 생성 (마스터 시드 초기화)
+    fn new(seed: &str) -> Self{
+        HDWallet {
+            master_seed: seed.to_string(),
+        }
+    }
+
+
+    // 2. 자식 키 생성 (Derivation)
+    // 원리: Hash(마스터 시드 + 순서 번호) = 자식 비밀키
+    fn derive_child_key(&self, index: u32) -> String {
+        let mut hasher = Sha256::new();
+        // 부모의 유전자(Seed)에 자식의 순서(Index)를 섞음
+        let input_data = format!("{}{}", self.master_seed, index);
+        hasher.update(input_data);
+
+        hex::encode(hasher.finalize())
+    }
+
+    // 마스터 키: Hash(마스터 시드) = 경로 m 자체 (자식이 아닌 뿌리)
+    fn master_key(&self) -> String {
+        hex::encode(Sha256::digest(self.master_seed.as_bytes()))
+    }
+
+    // 3. 경로 따라 내려가기 (m/0/5 = 0번 자식의 5번 자식, 빈 경로 m = 마스터 키)
+    // 원리: Hash(부모 키 + 순서 번호) = 손자 키
+    fn derive_path(&self, path: &[u32]) -> String {
+        let Some((first, rest)) = path.split_first() else {
+            return self.master_key();
+        };
+        rest.iter().fold(self.derive_child_key(*first), |parent_key, index| {
+            let mut hasher = Sha256::new();
+            hasher.update(format!("{}{}", parent_key, index));
+            hex::encode(hasher.finalize())
+        })
+    }
+
+    // 4. 주소 만들기: 비밀키 -> 공개키 -> 해시 앞 20바이트 (비밀키는 절대 공개 안 함)
+    fn derive_address(&self, path: &[u32]) -> String {
+        address_of(&self.public_key(path))
+    }
+}
+
+fn main() {
+    println!("--- [HD 지갑 시뮬레이션] ---");
+    // 1. 딱 하나의 마스터 시드만 기억하면 됨 (이것만 백업!)
+    let my_master_seed = "iron_man_secret_password_1234";
+    let my_wallet = HDWallet::new(my_master_seed);
+
+    println!("마스터 시드(뿌리): {}", my_master_seed);
+    println!("----------------------------------------------------------------");
+
+    // 2. 상황극: 월급날, 친구에게 돈 받을 때, 편의점 갈 때
+    // 매번 다른 주소를 쓰지만, 사실은 다 내 지갑임.
+
+    // 첫 번째 자식 (월급 통장용)
+    let child_key_1 = my_wallet.derive_child_key(1);
+    println!("[거래 1] 월급 수령용 주소 (Index 1):");
+    println!("-> {}", child_key_1);
+
+    // 두 번째 자식 (친구 송금용)
+    let child_key_2 = my_wallet.derive_child_key(2);
+    println!("\n[거래 2] 친구에게 받을 주소 (Index 2):");
+    println!(" -> {}", child_key_2);
+
+    let child_key_3 = my_wallet.derive_child_key(3);
+     println!("\n[거래 3] 치킨 사먹을 주소 (Index 3):");
+     println!("-> {}", child_key_3);
+
+     println!("--------------------------------------------------------------------");
+     println!("[해커의 시선]");
+     println!("해커: '이 주소 3개는 서로 완전히 다르게 생겼는데?'");
+     println!("해커: 'Index 1이 치킨을 사 먹었는지 알 수가 없네!' (추적 실패 ❌)");
+
+     println!("\n[나의 시선]");
+     println!("나: '핸드폰 잃어버려도 마스터 시드만 있으면 위 3개 키를 다시 계산해날 수 있어! (복구 가능 ✅)");
+
+    println!("\n--- [암호화 키스토어] 마스터 시드를 비밀번호로 잠가서 파일로 저장 ---");
+    let keystore_path = std::env::temp_dir().join("hd_wallet_keystore.json");
+    let password = "correct horse battery staple";
+    my_wallet
+        .save_encrypted(&keystore_path, password, KdfParams::light())
+        .expect("키스토어 저장 실패");
+    println!("저장 위치: {}", keystore_path.display());
+
+    // 1. 올바른 비밀번호 -> 같은 시드, 같은 자식 키
+    let restored = HDWallet::load_encrypted(&keystore_path, password).expect("키스토어 열기 실패");
+    assert_eq!(restored.derive_child_key(1), child_key_1);
+    println!("올바른 비밀번호: 복구 성공, Index 1 키 일치 ✅");
+
+    // 2. 틀린 비밀번호
+    match HDWallet::load_encrypted(&keystore_path, "wrong password") {
+        Err(KeystoreError::WrongPassword) => println!("틀린 비밀번호: 거부됨 ✅"),
+        _ => panic!("틀린 비밀번호를 잡아내지 못함"),
+    }
+
+    // 3. 해커가 파일 속 암호문 한 글자를 바꿔치기
+    let json = std::fs::read_to_string(&keystore_path).expect("키스토어 읽기 실패");
+    let mut file: serde_json::Value = serde_json::from_str(&json).expect("키스토어 JSON 파싱 실패");
+    let ciphertext = file["crypto"]["ciphertext"].as_str().unwrap().to_string();
+    let flipped = if ciphertext.starts_with('0') { "1" } else { "0" };
+    file["crypto"]["ciphertext"] = serde_json::Value::String(format!("{}{}", flipped, &ciphertext[1..]));
+    std::fs::write(&keystore_path, file.to_string()).expect("키스토어 쓰기 실패");
+
+    match HDWallet::load_encrypted(&keystore_path, password) {
+        Err(KeystoreError::Tampered) => println!("조작된 파일: MAC 불일치로 거부됨 ✅"),
+        _ => panic!("조작된 파일을 잡아내지 못함"),
+    }
+    let _ = std::fs::remove_file(&keystore_path);
+
+    println!("\n--- [지갑 복구] 새 핸드폰에서 마스터 시드로 잔고 되찾기 ---");
+    // 블록체인 대신 쓰는 로컬 장부: 주소 -> 잔고
+    let mut ledger = Ledger::new();
+    ledger.deposit(&my_wallet.derive_address(&[1]), 3_000_000); // 월급
+    ledger.deposit(&my_wallet.derive_address(&[2]), 50_000); // 친구 송금
+    ledger.deposit(&my_wallet.derive_address(&[3]), 20_000);
+    ledger.withdraw(&my_wallet.derive_address(&[3]), 20_000); // 치킨 사먹고 잔고 0 (그래도 '사용된' 주소)
+    ledger.deposit(&my_wallet.derive_address(&[12]), 777_000); // 한참 뒤 번호에 숨어있는 돈
+
+    // 1. 갭 5: 안 쓴 주소가 5개 연속 나오면 "더 없겠지" 하고 멈춤
+    let report = recover_funds(&restored, &ledger, &[], 5);
+    report.print();
+    println!("-> Index 4~8이 비어 있어서 Index 12의 돈은 못 찾음 🚨");
+
+    // 2. 갭을 넉넉하게 (비트코인 지갑 기본값은 20)
+    let report = recover_funds(&restored, &ledger, &[], 20);
+    report.print();
+    assert_eq!(report.total, 3_000_000 + 50_000 + 777_000);
+    println!("-> 갭을 늘리니 Index 12까지 찾음 (복구 가능 ✅)");
+
+    println!("\n--- [샤미르 비밀 분산] 마스터 시드를 5조각으로 나눠서 아무 3조각으로 복구 ---");
+    // 마스터 시드 하나만 백업하면, 그 종이 한 장을 잃어버리거나 도둑맞는 순간 끝
+    let backup: Vec<String> = my_wallet.split_seed(3, 5).iter().map(Share::encode).collect();
+    for (holder, share) in ["나", "엄마", "은행 금고", "변호사", "회사 서랍"].iter().zip(&backup) {
+        println!("[{}] {}", holder, share);
+    }
+
+    // 1. 조각 3개 (1, 3, 5번) -> 복구 성공
+    let picked: Vec<Share> = [0, 2, 4].iter().map(|&i| Share::decode(&backup[i]).unwrap()).collect();
+    let rebuilt = HDWallet::from_shares(&picked).expect("3조각으로 복구 실패");
+    assert_eq!(rebuilt.derive_child_key(1), child_key_1);
+    println!("\n조각 3개: 복구 성공, Index 1 키 일치 ✅");
+
+    // 2. 조각 2개 -> 다항식이 정해지지 않아서 복구 불가
+    match HDWallet::from_shares(&picked[..2]) {
+        Err(err @ ShamirError::NotEnoughShares { .. }) => println!("조각 2개: {} ✅", err),
+        _ => panic!("조각이 부족한데 복구됨"),
+    }
+
+    // 3. 종이에 적다가 한 글자 틀림 -> 체크섬으로 잡아냄
+    let mut smudged = backup[1].clone().into_bytes();
+    smudged[10] = if smudged[10] == b'0' { b'1' } else { b'0' };
+    match Share::decode(&String::from_utf8(smudged).unwrap()) {
+        Err(err @ ShamirError::CorruptedShare(_)) => println!("손상된 조각: {} ✅", err),
+        _ => panic!("손상된 조각을 잡아내지 못함"),
+    }
+
+    println!("\n--- [서명] 자식 키로 메시지와 거래에 서명하기 ---");
+    let message = "나는 Index 1 주소의 주인입니다".as_bytes();
+    let public_key_1 = my_wallet.public_key(&[1]);
+
+    // 1. ECDSA (RFC 6979): 같은 메시지 -> 항상 같은 서명
+    let ecdsa_a = my_wallet.sign_message(&[1], message, Scheme::Ecdsa);
+    let ecdsa_b = my_wallet.sign_message(&[1], message, Scheme::Ecdsa);
+    println!("ECDSA 서명 1: {}", ecdsa_a.to_hex());
+    println!("ECDSA 서명 2: {}", ecdsa_b.to_hex());
+    assert_eq!(ecdsa_a.to_hex(), ecdsa_b.to_hex());
+    assert!(verify_message(&public_key_1, message, &ecdsa_a));
+    println!("-> 두 번 서명해도 똑같음 (결정적 nonce), 검증 통과 ✅");
+
+    // 2. Schnorr (BIP-340): 보조 난수 때문에 매번 다르지만 둘 다 유효
+    let schnorr_a = my_wallet.sign_message(&[1], message, Scheme::Schnorr);
+    let schnorr_b = my_wallet.sign_message(&[1], message, Scheme::Schnorr);
+    println!("\nSchnorr 서명 1: {}", schnorr_a.to_hex());
+    println!("Schnorr 서명 2: {}", schnorr_b.to_hex());
+    assert!(verify_message(&public_key_1, message, &schnorr_a));
+    assert!(verify_message(&public_key_1, message, &schnorr_b));
+    assert!(!verify_message(&my_wallet.public_key(&[2]), message, &schnorr_a));
+    println!("-> 서명은 달라도 둘 다 검증 통과, 다른 주소의 공개키로는 실패 ✅");
+
+    // 3. 거래 서명: Index 1 -> Index 2 로 10만원
+    let tx = Transaction {
+        from: my_wallet.derive_address(&[1]),
+        to: my_wallet.derive_address(&[2]),
+        amount: 100_000,
+        nonce: 0,
+    };
+    for scheme in [Scheme::Ecdsa, Scheme::Schnorr] {
+        let mut signed = my_wallet.sign_transaction(&[1], tx.clone(), scheme);
+        assert!(signed.verify());
+
+        // 해커가 금액을 바꿔치기 -> sighash가 달라져서 서명 무효
+        signed.tx.amount = 9_999_999;
+        assert!(!signed.verify());
+    }
+    println!("\n거래 서명 검증 통과, 금액을 바꾸면 서명 무효 ✅");
+
+    // 4. Index 2의 키로 Index 1 주소의 돈을 쓰려고 하면 -> 주소와 공개키가 안 맞음
+    let forged = my_wallet.sign_transaction(&[2], tx, Scheme::Ecdsa);
+    assert!(!forged.verify());
+    println!("다른 키로 서명한 거래: 거부됨 ✅");
+
+    println!("\n--- [체인 분석] 해커가 거래 기록을 모아서 주소를 묶어보면? ---");
+    for (habit, label) in [
+        (SpendingHabit::CoSpend, "잔돈 모아 결제 (여러 UTXO 동시 사용)"),
+        (SpendingHabit::CoinControl, "코인 컨트롤 (결제마다 UTXO 하나)"),
+    ] {
+        let sim = simulate(&my_wallet, habit, 42);
+        let total = sim.wallet_addresses.len();
+        println!("\n[{}] 지갑 주소 {}개 사용, 거래 {}건", label, total, sim.txs.len());
+
+        for (use_change, heuristic) in [(false, "공통 입력 소유"), (true, "공통 입력 + 거스름돈 주소")] {
+            let mut clusters = Clusters::build(&sim.txs, use_change);
+            let (groups, largest) = clusters.summarize(&sim.wallet_addresses);
+            println!("  {} -> {}개 덩어리, 가장 큰 덩어리에 주소 {}/{}개", heuristic, groups, largest, total);
+        }
+
+        let mut clusters = Clusters::build(&sim.txs, true);
+        let chicken_payer = sim.chicken_tx.and_then(|tx| sim.txs[tx].inputs.first());
+        match chicken_payer {
+            Some(payer) if clusters.same_owner(&sim.wallet_addresses[0], payer) => {
+                println!("  해커: 'Index 1이 치킨을 사 먹었네!' (추적 성공 🚨)")
+            }
+            Some(_) => println!("  해커: 'Index 1이 치킨을 사 먹었는지 알 수가 없네!' (추적 실패 ❌)"),
+            None => println!("  (잔고가 모자라 치킨은 못 샀음)"),
+        }
+    }
+    println!("\n-> 주소를 매번 새로 만들어도, 같이 쓰는 순간 한 사람 것으로 묶임");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_path_is_the_master_key() {
+        let wallet = HDWallet::new("seed");
+        assert_eq!(wallet.derive_path(&[]), wallet.master_key());
+        assert_ne!(wallet.derive_path(&[]), wallet.derive_path(&[0]));
+        assert_eq!(wallet.derive_path(&[7]), wallet.derive_child_key(7));
+        // 마스터 키로도 서명용 주소를 만들 수 있음
+        assert_eq!(wallet.derive_address(&[]).len(), wallet.derive_address(&[0]).len());
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use super::HDWallet;
+
+// [장부] 블록체인(UTXO 집합) 대신 쓰는 로컬 장부
+// 주소가 한 번이라도 거래에 등장했으면 "사용된 주소" (잔고가 0이 되어도 기록은 남음)
+pub struct Ledger {
+    balances: HashMap<String, u64>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Ledger {
+            balances: HashMap::new(),
+        }
+    }
+
+    pub fn deposit(&mut self, address: &str, amount: u64) {
+        *self.balances.entry(address.to_string()).or_insert(0) += amount;
+    }
+
+    pub fn withdraw(&mut self, address: &str, amount: u64) {
+        let balance = self.balances.get_mut(address).expect("한 번도 입금 안 된 주소에서 출금 불가");
+        *balance = balance.checked_sub(amount).expect("잔고 부족");
+    }
+
+    // None = 한 번도 안 쓴 주소, Some(잔고) = 사용된 주소
+    pub fn lookup(&self, address: &str) -> Option<u64> {
+        self.balances.get(address).copied()
+    }
+}
+
+pub struct FoundFunds {
+    pub index: u32,
+    pub address: String,
+    pub balance: u64,
+}
+
+pub struct RecoveryReport {
+    pub found: Vec<FoundFunds>,
+    pub scanned: u32,
+    pub total: u64,
+}
+
+impl RecoveryReport {
+    pub fn print(&self) {
+        println!("주소 {}개 스캔, 사용된 주소 {}개 발견", self.scanned, self.found.len());
+        for funds in &self.found {
+            println!("  Index {:>2} | {} | 잔고 {}", funds.index, funds.address, funds.balance);
+        }
+        println!("  총 복구 금액: {}", self.total);
+    }
+}
+
+// [갭 리밋 스캔] 경로 끝 번호를 0, 1, 2, ... 올려가며 장부를 확인
+// 안 쓴 주소가 gap_limit개 연속으로 나오면 "이 뒤로는 없다"고 보고 멈춤
+pub fn recover_funds(wallet: &HDWallet, ledger: &Ledger, path_prefix: &[u32], gap_limit: u32) -> RecoveryReport {
+    let mut found = Vec::new();
+    let mut unused_in_a_row = 0;
+    let mut index = 0;
+
+    while unused_in_a_row < gap_limit {
+        let mut path = path_prefix.to_vec();
+        path.push(index);
+        let address = wallet.derive_address(&path);
+
+        match ledger.lookup(&address) {
+            Some(balance) => {
+                found.push(FoundFunds { index, address, balance });
+                unused_in_a_row = 0; // 사용된 주소를 만나면 갭 카운트 리셋
+            }
+            None => unused_in_a_row += 1,
+        }
+        index += 1;
+    }
+
+    let total = found.iter().map(|funds| funds.balance).sum();
+    RecoveryReport { found, scanned: index, total }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_stops_after_gap_limit() {
+        let wallet = HDWallet::new("test seed");
+        let mut ledger = Ledger::new();
+        for (index, amount) in [(0, 100), (3, 50), (25, 999)] {
+            ledger.deposit(&wallet.derive_address(&[0, index]), amount);
+        }
+
+        // 3번 뒤로 안 쓴 주소가 20개 이어지면 멈추므로 25번은 못 찾음
+        let report = recover_funds(&wallet, &ledger, &[0], 20);
+        assert_eq!(report.found.iter().map(|funds| funds.index).collect::<Vec<_>>(), [0, 3]);
+        assert_eq!(report.scanned, 24);
+        assert_eq!(report.total, 150);
+
+        let report = recover_funds(&wallet, &ledger, &[0], 30);
+        assert_eq!(report.total, 1_149);
+    }
+
+    #[test]
+    fn emptied_address_still_counts_as_used() {
+        let wallet = HDWallet::new("test seed");
+        let mut ledger = Ledger::new();
+        let spent = wallet.derive_address(&[0, 0]);
+        ledger.deposit(&spent, 10);
+        ledger.withdraw(&spent, 10);
+        ledger.deposit(&wallet.derive_address(&[0, 5]), 7);
+
+        let report = recover_funds(&wallet, &ledger, &[0], 5);
+        assert_eq!(report.found.len(), 2);
+        assert_eq!(report.total, 7);
+    }
+}
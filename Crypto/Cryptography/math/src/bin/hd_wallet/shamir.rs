@@ -0,0 +1,275 @@
+use std::fmt;
+
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+use super::HDWallet;
+
+// [샤미르 비밀 분산] 마스터 시드를 n조각으로 쪼개서 아무 k조각만 모이면 복구
+// 원리: 비밀을 k-1차 다항식의 f(0)에 숨기고, 조각 = (x, f(x))
+//       점 k개면 다항식이 딱 하나로 정해지지만, k-1개로는 f(0)에 대해 아무것도 알 수 없음
+// 계산은 바이트 단위로 GF(2^8) (AES와 같은 다항식 x^8 + x^4 + x^3 + x + 1) 위에서 함
+const CHECKSUM_LEN: usize = 4;
+const DIGEST_LEN: usize = 4;
+
+#[derive(Debug)]
+pub enum ShamirError {
+    NotEnoughShares { have: usize, need: usize },
+    CorruptedShare(u8),      // 조각 자체의 체크섬이 안 맞음
+    DuplicateShare(u8),      // 같은 번호 조각이 두 번 들어옴
+    ThresholdMismatch,       // 서로 다른 분할에서 나온 조각이 섞임
+    DigestMismatch,          // 조각은 멀쩡해 보이는데 복구한 시드의 지문이 다름
+    Malformed(String),
+}
+
+impl fmt::Display for ShamirError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShamirError::NotEnoughShares { have, need } => {
+                write!(f, "조각이 부족합니다 ({}개 있음, {}개 필요)", have, need)
+            }
+            ShamirError::CorruptedShare(id) => write!(f, "{}번 조각이 손상되었습니다", id),
+            ShamirError::DuplicateShare(id) => write!(f, "{}번 조각이 중복되었습니다", id),
+            ShamirError::ThresholdMismatch => write!(f, "서로 다른 분할의 조각이 섞였습니다"),
+            ShamirError::DigestMismatch => write!(f, "복구한 시드의 지문이 맞지 않습니다"),
+            ShamirError::Malformed(reason) => write!(f, "조각 형식 오류: {}", reason),
+        }
+    }
+}
+
+pub struct Share {
+    pub id: u8,        // x 좌표 (1..=n, 0은 비밀 자리라서 안 씀)
+    pub threshold: u8, // 복구에 필요한 조각 수 k
+    data: Vec<u8>,     // 바이트마다 f(x) 값
+}
+
+impl Share {
+    // 종이에 적어두기 좋은 한 줄 문자열: k | id | 데이터 | 체크섬 (모두 hex)
+    pub fn encode(&self) -> String {
+        format!(
+            "{:02x}{:02x}{}{}",
+            self.threshold,
+            self.id,
+            hex::encode(&self.data),
+            hex::encode(self.checksum())
+        )
+    }
+
+    pub fn decode(text: &str) -> Result<Self, ShamirError> {
+        let bytes = hex::decode(text.trim()).map_err(|_| ShamirError::Malformed("hex가 아님".to_string()))?;
+        if bytes.len() < 2 + CHECKSUM_LEN + 1 {
+            return Err(ShamirError::Malformed("너무 짧음".to_string()));
+        }
+        let (body, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        let share = Share {
+            threshold: body[0],
+            id: body[1],
+            data: body[2..].to_vec(),
+        };
+        if share.checksum() != checksum {
+            return Err(ShamirError::CorruptedShare(share.id));
+        }
+        Ok(share)
+    }
+
+    fn checksum(&self) -> [u8; CHECKSUM_LEN] {
+        let mut hasher = Sha256::new();
+        hasher.update([self.threshold, self.id]);
+        hasher.update(&self.data);
+        let mut checksum = [0u8; CHECKSUM_LEN];
+        checksum.copy_from_slice(&hasher.finalize()[..CHECKSUM_LEN]);
+        checksum
+    }
+}
+
+// 1. 쪼개기: 비밀 뒤에 지문(SHA-256 앞 4바이트)을 붙여서 같이 나눔
+pub fn split(secret: &[u8], threshold: u8, share_count: u8) -> Vec<Share> {
+    assert!(threshold >= 1 && threshold <= share_count, "1 <= k <= n 이어야 함");
+
+    let mut payload = secret.to_vec();
+    payload.extend_from_slice(&Sha256::digest(secret)[..DIGEST_LEN]);
+
+    let mut rng = rand::thread_rng();
+    let mut shares: Vec<Share> = (1..=share_count)
+        .map(|id| Share { id, threshold, data: Vec::with_capacity(payload.len()) })
+        .collect();
+
+    for &byte in &payload {
+        // f(x) = byte + a1*x + ... + a(k-1)*x^(k-1), 계수는 매 바이트마다 새로 뽑은 랜덤
+        let mut coefficients = vec![byte];
+        coefficients.extend((1..threshold).map(|_| rng.gen_range(0..=255u8)));
+        for share in shares.iter_mut() {
+            share.data.push(evaluate(&coefficients, share.id));
+        }
+    }
+    shares
+}
+
+// 2. 합치기: 라그랑주 보간으로 f(0)을 되살리고 지문 확인
+pub fn combine(shares: &[Share]) -> Result<Vec<u8>, ShamirError> {
+    let first = shares.first().ok_or(ShamirError::NotEnoughShares { have: 0, need: 1 })?;
+    let threshold = first.threshold as usize;
+
+    for (i, share) in shares.iter().enumerate() {
+        if share.id == 0 {
+            return Err(ShamirError::Malformed("조각 번호 0은 쓸 수 없음".to_string()));
+        }
+        if share.threshold != first.threshold || share.data.len() != first.data.len() {
+            return Err(ShamirError::ThresholdMismatch);
+        }
+        if shares[..i].iter().any(|other| other.id == share.id) {
+            return Err(ShamirError::DuplicateShare(share.id));
+        }
+    }
+    if shares.len() < threshold {
+        return Err(ShamirError::NotEnoughShares { have: shares.len(), need: threshold });
+    }
+
+    let used = &shares[..threshold];
+    let payload: Vec<u8> = (0..first.data.len())
+        .map(|position| interpolate_at_zero(used, position))
+        .collect();
+    if payload.len() < DIGEST_LEN {
+        return Err(ShamirError::Malformed("데이터가 너무 짧음".to_string()));
+    }
+
+    let (secret, digest) = payload.split_at(payload.len() - DIGEST_LEN);
+    if &Sha256::digest(secret)[..DIGEST_LEN] != digest {
+        return Err(ShamirError::DigestMismatch);
+    }
+    Ok(secret.to_vec())
+}
+
+impl HDWallet {
+    // 마스터 시드를 k-of-n 조각으로 백업
+    pub fn split_seed(&self, threshold: u8, share_count: u8) -> Vec<Share> {
+        split(self.master_seed.as_bytes(), threshold, share_count)
+    }
+
+    // 조각들로 지갑 복구
+    pub fn from_shares(shares: &[Share]) -> Result<Self, ShamirError> {
+        let seed = combine(shares)?;
+        let seed = String::from_utf8(seed).map_err(|_| ShamirError::Malformed("시드가 UTF-8이 아님".to_string()))?;
+        Ok(HDWallet::new(&seed))
+    }
+}
+
+// L(0) = sum( y_i * prod_{j != i} x_j / (x_j - x_i) ), GF(2^8)에서는 뺄셈도 XOR
+fn interpolate_at_zero(shares: &[Share], position: usize) -> u8 {
+    let mut result = 0u8;
+    for (i, share_i) in shares.iter().enumerate() {
+        let mut numerator = 1u8;
+        let mut denominator = 1u8;
+        for (j, share_j) in shares.iter().enumerate() {
+            if i != j {
+                numerator = gf_mul(numerator, share_j.id);
+                denominator = gf_mul(denominator, share_j.id ^ share_i.id);
+            }
+        }
+        let basis = gf_mul(numerator, gf_inv(denominator));
+        result ^= gf_mul(share_i.data[position], basis);
+    }
+    result
+}
+
+// 호너법: ((a(k-1) * x + a(k-2)) * x + ...) + a0
+fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+    coefficients.iter().rev().fold(0u8, |acc, &c| gf_mul(acc, x) ^ c)
+}
+
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1b; // x^8 = x^4 + x^3 + x + 1
+        }
+        b >>= 1;
+    }
+    product
+}
+
+// a^254 = a^-1 (0이 아닌 원소 255개가 곱셈군을 이룸)
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    for _ in 0..254 {
+        result = gf_mul(result, a);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"correct horse battery staple";
+
+    // 종이에 적었다가 다시 읽어 들인 조각
+    fn copy(share: &Share) -> Share {
+        Share::decode(&share.encode()).unwrap()
+    }
+
+    #[test]
+    fn any_k_shares_reconstruct() {
+        let shares = split(SECRET, 3, 5);
+        for a in 0..5 {
+            for b in a + 1..5 {
+                for c in b + 1..5 {
+                    let picked = [copy(&shares[a]), copy(&shares[b]), copy(&shares[c])];
+                    assert_eq!(combine(&picked).unwrap(), SECRET);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn threshold_one_and_n_of_n() {
+        assert_eq!(combine(&split(SECRET, 1, 3)[2..]).unwrap(), SECRET);
+        assert_eq!(combine(&split(SECRET, 4, 4)).unwrap(), SECRET);
+    }
+
+    #[test]
+    fn fewer_than_k_shares_are_rejected() {
+        let shares = split(SECRET, 3, 5);
+        assert!(matches!(combine(&shares[..2]), Err(ShamirError::NotEnoughShares { have: 2, need: 3 })));
+        assert!(matches!(combine(&[]), Err(ShamirError::NotEnoughShares { have: 0, .. })));
+    }
+
+    #[test]
+    fn checksum_catches_a_typo() {
+        let mut text = split(SECRET, 2, 3)[1].encode().into_bytes();
+        text[10] = if text[10] == b'0' { b'1' } else { b'0' };
+        let text = String::from_utf8(text).unwrap();
+        assert!(matches!(Share::decode(&text), Err(ShamirError::CorruptedShare(2))));
+    }
+
+    #[test]
+    fn digest_catches_a_consistent_forgery() {
+        // 체크섬까지 새로 맞춘 조각은 decode를 통과하지만, 복구한 시드의 지문이 안 맞음
+        let mut shares = split(SECRET, 2, 3);
+        shares[0].data[0] ^= 1;
+        let shares = [copy(&shares[0]), copy(&shares[1])];
+        assert!(matches!(combine(&shares), Err(ShamirError::DigestMismatch)));
+    }
+
+    #[test]
+    fn duplicate_and_mixed_shares_are_rejected() {
+        let first = split(SECRET, 2, 3);
+        let second = split(SECRET, 3, 3);
+        let duplicate = [copy(&first[0]), copy(&first[0])];
+        assert!(matches!(combine(&duplicate), Err(ShamirError::DuplicateShare(1))));
+        let mixed = [copy(&first[0]), copy(&second[1])];
+        assert!(matches!(combine(&mixed), Err(ShamirError::ThresholdMismatch)));
+    }
+
+    #[test]
+    fn gf_inverse() {
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1);
+        }
+    }
+}
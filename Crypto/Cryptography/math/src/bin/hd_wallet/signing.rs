@@ -0,0 +1,185 @@
+use k256::ecdsa;
+use k256::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier};
+use k256::schnorr;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+use super::HDWallet;
+
+// [서명] 자식 비밀키(32바이트)를 secp256k1 비밀키로 그대로 사용
+// - ECDSA: 비트코인/이더리움의 고전 서명. nonce k를 RFC 6979로 (비밀키 + 메시지)에서 결정적으로 만듦
+//          -> 같은 메시지는 항상 같은 서명, 나쁜 난수 때문에 비밀키가 새는 사고(PS3 해킹)가 없음
+// - Schnorr: BIP-340 (탭루트). 32바이트 x좌표 공개키, 64바이트 서명
+// 두 방식 모두 32바이트 다이제스트(메시지의 SHA-256 또는 거래의 sighash)에 서명함
+#[derive(Clone, Copy)]
+pub enum Scheme {
+    Ecdsa,
+    Schnorr,
+}
+
+pub enum WalletSignature {
+    Ecdsa(ecdsa::Signature),
+    Schnorr(schnorr::Signature),
+}
+
+impl WalletSignature {
+    pub fn to_hex(&self) -> String {
+        match self {
+            WalletSignature::Ecdsa(sig) => hex::encode(sig.to_bytes()),
+            WalletSignature::Schnorr(sig) => hex::encode(sig.to_bytes()),
+        }
+    }
+}
+
+// 간단한 송금 거래: 누가, 누구에게, 얼마를, 몇 번째로
+#[derive(Clone)]
+pub struct Transaction {
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub nonce: u64,
+}
+
+impl Transaction {
+    // 서명 대상이 되는 거래 지문 (필드 하나만 바뀌어도 완전히 달라짐)
+    pub fn sighash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(format!("{}|{}|{}|{}", self.from, self.to, self.amount, self.nonce));
+        hasher.finalize().into()
+    }
+}
+
+pub struct SignedTransaction {
+    pub tx: Transaction,
+    pub public_key: Vec<u8>, // 33바이트 압축 공개키
+    pub signature: WalletSignature,
+}
+
+impl SignedTransaction {
+    // 1. 공개키가 보내는 주소의 주인인지  2. 서명이 이 거래 내용에 대한 것인지
+    pub fn verify(&self) -> bool {
+        address_of(&self.public_key) == self.tx.from
+            && verify_digest(&self.public_key, &self.tx.sighash(), &self.signature)
+    }
+}
+
+impl HDWallet {
+    pub fn ecdsa_key(&self, path: &[u32]) -> ecdsa::SigningKey {
+        ecdsa::SigningKey::from_slice(&self.secret_bytes(path)).expect("자식 키가 secp256k1 범위를 벗어남")
+    }
+
+    pub fn schnorr_key(&self, path: &[u32]) -> schnorr::SigningKey {
+        schnorr::SigningKey::from_bytes(&self.secret_bytes(path)).expect("자식 키가 secp256k1 범위를 벗어남")
+    }
+
+    // 33바이트 압축 공개키 (02/03 + x좌표)
+    pub fn public_key(&self, path: &[u32]) -> Vec<u8> {
+        self.ecdsa_key(path).verifying_key().to_encoded_point(true).as_bytes().to_vec()
+    }
+
+    pub fn sign_message(&self, path: &[u32], message: &[u8], scheme: Scheme) -> WalletSignature {
+        self.sign_digest(path, &Sha256::digest(message).into(), scheme)
+    }
+
+    pub fn sign_transaction(&self, path: &[u32], tx: Transaction, scheme: Scheme) -> SignedTransaction {
+        let signature = self.sign_digest(path, &tx.sighash(), scheme);
+        SignedTransaction {
+            tx,
+            public_key: self.public_key(path),
+            signature,
+        }
+    }
+
+    fn sign_digest(&self, path: &[u32], digest: &[u8; 32], scheme: Scheme) -> WalletSignature {
+        match scheme {
+            Scheme::Ecdsa => {
+                let sig: ecdsa::Signature = self.ecdsa_key(path).sign_prehash(digest).expect("ECDSA 서명 실패");
+                WalletSignature::Ecdsa(sig)
+            }
+            Scheme::Schnorr => {
+                // BIP-340 권장: 보조 난수(aux_rand)를 섞어서 부채널 공격에 대비
+                let mut aux_rand = [0u8; 32];
+                rand::thread_rng().fill(&mut aux_rand);
+                let sig = self.schnorr_key(path).sign_raw(digest, &aux_rand).expect("Schnorr 서명 실패");
+                WalletSignature::Schnorr(sig)
+            }
+        }
+    }
+
+    fn secret_bytes(&self, path: &[u32]) -> Vec<u8> {
+        hex::decode(self.derive_path(path)).expect("자식 키는 항상 hex")
+    }
+}
+
+pub fn verify_message(public_key: &[u8], message: &[u8], signature: &WalletSignature) -> bool {
+    verify_digest(public_key, &Sha256::digest(message).into(), signature)
+}
+
+fn verify_digest(public_key: &[u8], digest: &[u8; 32], signature: &WalletSignature) -> bool {
+    match signature {
+        WalletSignature::Ecdsa(sig) => ecdsa::VerifyingKey::from_sec1_bytes(public_key)
+            .map(|key| key.verify_prehash(digest, sig).is_ok())
+            .unwrap_or(false),
+        // BIP-340 공개키는 x좌표만 씀 (압축 공개키에서 앞의 02/03 한 바이트를 떼면 됨)
+        WalletSignature::Schnorr(sig) => public_key
+            .get(1..)
+            .and_then(|x_only| schnorr::VerifyingKey::from_bytes(x_only).ok())
+            .map(|key| key.verify_raw(digest, sig).is_ok())
+            .unwrap_or(false),
+    }
+}
+
+// 주소 = SHA-256(압축 공개키) 앞 20바이트
+pub fn address_of(public_key: &[u8]) -> String {
+    format!("addr_{}", hex::encode(&Sha256::digest(public_key)[..20]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payment(wallet: &HDWallet) -> Transaction {
+        Transaction { from: wallet.derive_address(&[0, 1]), to: "addr_shop".to_string(), amount: 500, nonce: 7 }
+    }
+
+    #[test]
+    fn ecdsa_is_deterministic() {
+        // RFC 6979: 같은 키 + 같은 메시지 = 항상 같은 서명
+        let wallet = HDWallet::new("test seed");
+        let first = wallet.sign_message(&[0, 1], b"hello", Scheme::Ecdsa);
+        let second = wallet.sign_message(&[0, 1], b"hello", Scheme::Ecdsa);
+        assert_eq!(first.to_hex(), second.to_hex());
+    }
+
+    #[test]
+    fn message_signatures_verify_only_for_their_message_and_key() {
+        let wallet = HDWallet::new("test seed");
+        for scheme in [Scheme::Ecdsa, Scheme::Schnorr] {
+            let signature = wallet.sign_message(&[0, 1], b"hello", scheme);
+            assert!(verify_message(&wallet.public_key(&[0, 1]), b"hello", &signature));
+            assert!(!verify_message(&wallet.public_key(&[0, 1]), b"hellp", &signature));
+            assert!(!verify_message(&wallet.public_key(&[0, 2]), b"hello", &signature));
+            assert!(!verify_message(&[], b"hello", &signature));
+        }
+    }
+
+    #[test]
+    fn transaction_signatures_cover_the_contents() {
+        let wallet = HDWallet::new("test seed");
+        for scheme in [Scheme::Ecdsa, Scheme::Schnorr] {
+            let signed = wallet.sign_transaction(&[0, 1], payment(&wallet), scheme);
+            assert!(signed.verify());
+
+            let mut altered = wallet.sign_transaction(&[0, 1], payment(&wallet), scheme);
+            altered.tx.amount += 1;
+            assert!(!altered.verify());
+
+            let mut redirected = wallet.sign_transaction(&[0, 1], payment(&wallet), scheme);
+            redirected.tx.to = "addr_mallory".to_string();
+            assert!(!redirected.verify());
+
+            // 다른 주소의 키로 서명하면 보내는 주소와 공개키가 안 맞음
+            assert!(!wallet.sign_transaction(&[0, 2], payment(&wallet), scheme).verify());
+        }
+    }
+}